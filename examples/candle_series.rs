@@ -0,0 +1,44 @@
+use bybit::error::BybitError;
+use bybit::kline::CandleSeries;
+use bybit::ws::response::FuturePublicResponse;
+use bybit::{Category, KlineInterval, RestApiClient, WebSocketApiClient};
+use futures::future::BoxFuture;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let symbol = "ETHUSDT";
+    let interval = KlineInterval::Min1;
+
+    // Backfill the history first, then keep the series up to date with the kline topic.
+    let mut series =
+        CandleSeries::new(Category::Linear, symbol, interval, 200).confirmed_only(true);
+    let rest_client = RestApiClient::builder().build();
+    match series.backfill(&rest_client).await {
+        Ok(n) => println!("Backfilled {} candles of {}", n, series.topic()),
+        Err(e) => eprintln!("Error: {e}"),
+    }
+
+    let mut client = WebSocketApiClient::future_linear().build();
+    client.subscribe_kline(symbol, interval);
+
+    let callback = Box::new(
+        move |res: FuturePublicResponse<'_>| -> BoxFuture<'static, Result<(), BybitError>> {
+            if let FuturePublicResponse::Kline(res) = res {
+                for kline in &res.data {
+                    match series.update(kline) {
+                        Ok(Some(candle)) => println!("Confirmed: {:?}", candle),
+                        Ok(None) => {}
+                        Err(e) => eprintln!("Error: {e}"),
+                    }
+                }
+            }
+            Box::pin(async move { Ok(()) })
+        },
+    );
+
+    if let Err(e) = client.run(callback).await {
+        eprintln!("Error: {e}");
+    }
+}
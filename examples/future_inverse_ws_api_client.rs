@@ -1,5 +1,4 @@
 use bybit::ws::future;
-use bybit::ws::response::FuturePublicResponse;
 use bybit::KlineInterval;
 use bybit::WebSocketApiClient;
 
@@ -16,6 +15,4 @@ fn main() {
     client.subscribe_ticker(symbol);
     client.subscribe_kline(symbol, KlineInterval::Min1);
     client.subscribe_liquidation(symbol);
-
-
 }
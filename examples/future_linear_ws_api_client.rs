@@ -1,5 +1,4 @@
 use bybit::ws::future;
-use bybit::ws::response::FuturePublicResponse;
 use bybit::KlineInterval;
 use bybit::WebSocketApiClient;
 
@@ -16,6 +15,8 @@ fn main() {
     client.subscribe_ticker(symbol);
     client.subscribe_kline(symbol, KlineInterval::Min1);
     client.subscribe_liquidation(symbol);
-
-
+    client.subscribe_all_liquidation(symbol);
+    client.subscribe_price_limit(symbol);
+    client.subscribe_insurance("USDT");
+    client.subscribe_adl_alert("USDT");
 }
@@ -1,31 +1,43 @@
-use bybit::ws::response::{OrderbookItem, SpotPublicResponse};
+use bybit::error::BybitError;
+use bybit::orderbook::LocalOrderbook;
+use bybit::ws::response::SpotPublicResponse;
 use bybit::ws::spot;
 use bybit::WebSocketApiClient;
-use std::io::{self, Write};
+use futures::future::BoxFuture;
 
-struct OwnedOrderBookItem(String, String);
+#[tokio::main]
+async fn main() {
+    env_logger::init();
 
-impl<'a> From<&OrderbookItem<'a>> for OwnedOrderBookItem {
-    fn from(value: &OrderbookItem) -> Self {
-        OwnedOrderBookItem(value.0.to_owned(), value.1.to_owned())
-    }
-}
-
-fn main() {
     let mut client = WebSocketApiClient::spot().build();
 
     let symbol = "ETHUSDT";
 
-    client.subscribe_trade(symbol);
     client.subscribe_orderbook(symbol, spot::OrderbookDepth::Level50);
 
-    let stdout = io::stdout();
-    let mut handle = io::BufWriter::new(stdout);
-
-    let mut latest_price: String = String::new();
-    let mut direction = "△";
-    let mut asks: Vec<OwnedOrderBookItem> = Vec::new();
-    let mut bids: Vec<OwnedOrderBookItem> = Vec::new();
-
-  
+    // Resubscribe the orderbook to receive a fresh snapshot when a gap is detected.
+    let mut book = LocalOrderbook::new(symbol)
+        .resubscriber(client.resubscriber())
+        .on_event(|e| println!("Orderbook event: {:?}", e));
+    let callback = Box::new(
+        move |res: SpotPublicResponse<'_>| -> BoxFuture<'static, Result<(), BybitError>> {
+            if let SpotPublicResponse::Orderbook(res) = res {
+                match book.apply(&res) {
+                    Ok(()) => println!(
+                        "u: {}, best bid: {:?}, best ask: {:?}, spread: {:?}",
+                        book.update_id(),
+                        book.best_bid(),
+                        book.best_ask(),
+                        book.spread()
+                    ),
+                    Err(e) => eprintln!("Error: {e}"),
+                }
+            }
+            Box::pin(async move { Ok(()) })
+        },
+    );
+
+    if let Err(e) = client.run(callback).await {
+        eprintln!("Error: {e}");
+    }
 }
@@ -1,5 +1,4 @@
 use bybit::ws::option;
-use bybit::ws::response::OptionPublicResponse;
 use bybit::WebSocketApiClient;
 
 fn main() {
@@ -14,6 +13,4 @@ fn main() {
     client.subscribe_orderbook(symbol, option::OrderbookDepth::Level100);
     client.subscribe_trade(base_coin);
     client.subscribe_ticker(symbol);
-
-
 }
@@ -1,4 +1,3 @@
-use bybit::ws::response::PrivateResponse;
 use bybit::WebSocketApiClient;
 use std::env;
 
@@ -17,6 +16,4 @@ fn main() {
     client.subscribe_order();
     client.subscribe_wallet();
     client.subscribe_greek();
-
- 
 }
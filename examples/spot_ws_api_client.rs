@@ -1,9 +1,5 @@
-use std::future::Future;
-use std::pin::Pin;
-
 use bybit::error::BybitError;
 use bybit::ws::response::SpotPublicResponse;
-use bybit::ws::response::SpotPublicResponseArg;
 use bybit::ws::spot;
 use bybit::KlineInterval;
 use bybit::WebSocketApiClient;
@@ -27,14 +23,14 @@ async fn main() {
     client.subscribe_lt_ticker(lt_symbol);
     client.subscribe_lt_nav(lt_symbol);
 
-    let callback: Box<dyn FnMut(SpotPublicResponseArg) -> Pin<Box<dyn Future<Output = Result<(), BybitError>> + Send>> + Send> =
-    Box::new(|res: SpotPublicResponseArg| {
-        Box::pin(async move {
-            // Process `res` here
-            println!("Received: ");
-            Ok(())
-        })
-    });
+    let callback = Box::new(
+        |res: SpotPublicResponse<'_>| -> BoxFuture<'static, Result<(), BybitError>> {
+            // Process `res` here. It borrows the received message, so copy
+            // what is needed before moving into the future.
+            println!("Received: {:?}", res);
+            Box::pin(async move { Ok(()) })
+        },
+    );
 
     // Assuming `client` is properly defined elsewhere and `run` matches the expected signature
     if let Err(e) = client.run(callback).await {
@@ -0,0 +1,36 @@
+use bybit::enums::{Side, TimeInForce};
+use bybit::ws::trade::{CancelOrderRequest, CreateOrderRequest};
+use bybit::{Category, WebSocketApiClient};
+use std::env;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let api_key: String = env::var("BYBIT_API_KEY").unwrap();
+    let secret: String = env::var("BYBIT_SECRET").unwrap();
+
+    let client = WebSocketApiClient::trade()
+        .testnet()
+        .build_with_credentials(api_key, secret);
+
+    let conn = match client.connect().await {
+        Ok(conn) => conn,
+        Err(e) => return eprintln!("Error: {e}"),
+    };
+
+    let order = CreateOrderRequest::limit(Category::Linear, "ETHUSDT", Side::Buy, "0.01", "1000")
+        .time_in_force(TimeInForce::PostOnly)
+        .order_link_id("rust-bybit-example");
+    match conn.create_order(&order).await {
+        Ok(res) => println!("Created: {:?}", res.data),
+        Err(e) => return eprintln!("Error: {e}"),
+    }
+
+    let cancel =
+        CancelOrderRequest::new(Category::Linear, "ETHUSDT").order_link_id("rust-bybit-example");
+    match conn.cancel_order(&cancel).await {
+        Ok(res) => println!("Cancelled: {:?}", res.data),
+        Err(e) => eprintln!("Error: {e}"),
+    }
+}
@@ -0,0 +1,539 @@
+//! Positions and wallet balances cached from REST snapshots and the private
+//! `position` and `wallet` topics.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::broadcast;
+
+use crate::enums::{Category, Side};
+use crate::error::Result;
+use crate::num::ToF64;
+use crate::rest::account::{WalletBalance, WalletBalanceCoin};
+use crate::rest::position::PositionInfo;
+use crate::timestamp::{from_millis, Timestamp, ToMillis};
+use crate::ws::response::{Position, PrivateResponse, Wallet, WalletCoin};
+use crate::RestApiClient;
+
+/// Identifies a position. `position_idx` is 0 in one-way mode, 1 for the Buy side and
+/// 2 for the Sell side in hedge mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PositionKey {
+    pub category: Category,
+    pub symbol: String,
+    pub position_idx: u8,
+}
+
+impl PositionKey {
+    pub fn new<S: Into<String>>(category: Category, symbol: S, position_idx: u8) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            position_idx,
+        }
+    }
+}
+
+/// An open position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionState {
+    pub side: Option<Side>,
+    pub size: f64,
+    /// Average entry price.
+    pub entry_price: f64,
+    pub mark_price: f64,
+    pub position_value: f64,
+    /// `None` in portfolio margin mode.
+    pub leverage: Option<f64>,
+    /// `None` if there is no estimated liquidation price.
+    pub liq_price: Option<f64>,
+    /// Initial margin. `None` in portfolio margin mode.
+    pub position_im: Option<f64>,
+    /// Maintenance margin. `None` in portfolio margin mode.
+    pub position_mm: Option<f64>,
+    pub unrealised_pnl: f64,
+    pub cum_realised_pnl: f64,
+    pub updated_time: Timestamp,
+}
+
+impl PositionState {
+    fn from_ws(position: &Position) -> Self {
+        Self {
+            side: position.side.clone(),
+            size: position.size.to_f64().unwrap_or_default(),
+            entry_price: position.entry_price.to_f64().unwrap_or_default(),
+            mark_price: position.mark_price.to_f64().unwrap_or_default(),
+            position_value: position.position_value.to_f64().unwrap_or_default(),
+            leverage: position.leverage.to_f64(),
+            liq_price: position.liq_price.to_f64(),
+            position_im: position.position_im.to_f64(),
+            position_mm: position.position_mm.to_f64(),
+            unrealised_pnl: position.unrealised_pnl.to_f64().unwrap_or_default(),
+            cum_realised_pnl: position.cum_realised_pnl.to_f64().unwrap_or_default(),
+            updated_time: position.updated_time,
+        }
+    }
+
+    fn from_rest(position: &PositionInfo) -> Self {
+        Self {
+            side: position.side.clone(),
+            size: position.size.to_f64().unwrap_or_default(),
+            entry_price: position.avg_price.to_f64().unwrap_or_default(),
+            mark_price: position.mark_price.to_f64().unwrap_or_default(),
+            position_value: position.position_value.to_f64().unwrap_or_default(),
+            leverage: position.leverage.to_f64(),
+            liq_price: position.liq_price.to_f64(),
+            position_im: position.position_im.to_f64(),
+            position_mm: position.position_mm.to_f64(),
+            unrealised_pnl: position.unrealised_pnl.to_f64().unwrap_or_default(),
+            cum_realised_pnl: position.cum_realised_pnl.to_f64().unwrap_or_default(),
+            updated_time: position.updated_time,
+        }
+    }
+}
+
+/// The balance of a coin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoinBalance {
+    pub equity: f64,
+    pub usd_value: f64,
+    pub wallet_balance: f64,
+    /// `None` if it is not returned, e.g. in unified accounts.
+    pub available_to_withdraw: Option<f64>,
+    /// Pre-occupied margin for orders.
+    pub total_order_im: Option<f64>,
+    pub total_position_im: Option<f64>,
+    pub total_position_mm: Option<f64>,
+    pub unrealised_pnl: f64,
+    pub cum_realised_pnl: f64,
+}
+
+impl CoinBalance {
+    fn from_ws(coin: &WalletCoin) -> Self {
+        Self {
+            equity: coin.equity.to_f64().unwrap_or_default(),
+            usd_value: coin.usd_value.to_f64().unwrap_or_default(),
+            wallet_balance: coin.wallet_balance.to_f64().unwrap_or_default(),
+            available_to_withdraw: coin.available_to_withdraw.to_f64(),
+            total_order_im: coin.total_order_im.to_f64(),
+            total_position_im: coin.total_position_im.to_f64(),
+            total_position_mm: coin.total_position_mm.to_f64(),
+            unrealised_pnl: coin.unrealised_pnl.to_f64().unwrap_or_default(),
+            cum_realised_pnl: coin.cum_realised_pnl.to_f64().unwrap_or_default(),
+        }
+    }
+
+    fn from_rest(coin: &WalletBalanceCoin) -> Self {
+        Self {
+            equity: coin.equity.to_f64().unwrap_or_default(),
+            usd_value: coin.usd_value.to_f64().unwrap_or_default(),
+            wallet_balance: coin.wallet_balance.to_f64().unwrap_or_default(),
+            available_to_withdraw: coin.available_to_withdraw.to_f64(),
+            total_order_im: coin.total_order_im.to_f64(),
+            total_position_im: coin.total_position_im.to_f64(),
+            total_position_mm: coin.total_position_mm.to_f64(),
+            unrealised_pnl: coin.unrealised_pnl.to_f64().unwrap_or_default(),
+            cum_realised_pnl: coin.cum_realised_pnl.to_f64().unwrap_or_default(),
+        }
+    }
+}
+
+/// The account-wide balances converted to USD. The fields are `None` in non-unified mode.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountSummary {
+    pub account_type: String,
+    pub equity: Option<f64>,
+    pub wallet_balance: Option<f64>,
+    pub margin_balance: Option<f64>,
+    pub available_balance: Option<f64>,
+    pub initial_margin: Option<f64>,
+    pub maintenance_margin: Option<f64>,
+    /// Initial margin rate.
+    pub im_rate: Option<f64>,
+    /// Maintenance margin rate.
+    pub mm_rate: Option<f64>,
+    /// Unrealised PnL of perpetuals.
+    pub perp_upl: Option<f64>,
+}
+
+impl AccountSummary {
+    fn from_ws(wallet: &Wallet) -> Self {
+        Self {
+            account_type: wallet.account_type.to_owned(),
+            equity: wallet.total_equity.to_f64(),
+            wallet_balance: wallet.total_wallet_balance.to_f64(),
+            margin_balance: wallet.total_margin_balance.to_f64(),
+            available_balance: wallet.total_available_balance.to_f64(),
+            initial_margin: wallet.total_initial_margin.to_f64(),
+            maintenance_margin: wallet.total_maintenance_margin.to_f64(),
+            im_rate: wallet.account_im_rate.to_f64(),
+            mm_rate: wallet.account_mm_rate.to_f64(),
+            perp_upl: wallet.total_perp_upl.to_f64(),
+        }
+    }
+
+    fn from_rest(wallet: &WalletBalance) -> Self {
+        Self {
+            account_type: wallet.account_type.clone(),
+            equity: wallet.total_equity.to_f64(),
+            wallet_balance: wallet.total_wallet_balance.to_f64(),
+            margin_balance: wallet.total_margin_balance.to_f64(),
+            available_balance: wallet.total_available_balance.to_f64(),
+            initial_margin: wallet.total_initial_margin.to_f64(),
+            maintenance_margin: wallet.total_maintenance_margin.to_f64(),
+            im_rate: wallet.account_im_rate.to_f64(),
+            mm_rate: wallet.account_mm_rate.to_f64(),
+            perp_upl: wallet.total_perp_upl.to_f64(),
+        }
+    }
+}
+
+/// A consistent view of the account at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountSnapshot {
+    /// `None` until a wallet snapshot or update is received.
+    pub summary: Option<AccountSummary>,
+    /// Balances keyed by coin.
+    pub coins: HashMap<String, CoinBalance>,
+    /// Open positions. Closed positions are removed.
+    pub positions: HashMap<PositionKey, PositionState>,
+    /// The `updated_time` of the closed positions, so that stale updates do not reopen them.
+    closed: HashMap<PositionKey, Timestamp>,
+}
+
+impl AccountSnapshot {
+    /// The sum of the unrealised PnL of the positions. The positions may be margined in
+    /// different coins.
+    pub fn unrealised_pnl(&self) -> f64 {
+        self.positions.values().map(|p| p.unrealised_pnl).sum()
+    }
+
+    /// Return `false` if the update is stale or changes nothing. Updates of a closed
+    /// position must be newer than its closing update.
+    fn update_position(&mut self, key: PositionKey, position: PositionState) -> bool {
+        let time = position.updated_time.to_millis();
+        if self.closed.get(&key).is_some_and(|t| time <= t.to_millis()) {
+            return false;
+        }
+        match self.positions.get(&key) {
+            Some(last) if time < last.updated_time.to_millis() => false,
+            Some(last) if *last == position => false,
+            _ if position.size == 0.0 => self.close_position(key, position.updated_time),
+            _ => {
+                self.closed.remove(&key);
+                self.positions.insert(key, position);
+                true
+            }
+        }
+    }
+
+    /// Remove a position closed at `time`. Return `false` if it was not open.
+    fn close_position(&mut self, key: PositionKey, time: Timestamp) -> bool {
+        let removed = self.positions.remove(&key).is_some();
+        let closed = self.closed.entry(key).or_insert(time);
+        if time.to_millis() > closed.to_millis() {
+            *closed = time;
+        }
+        removed
+    }
+
+    /// The key of a position from the private topic. The category is absent in unified
+    /// accounts, so it is taken from a known position of the symbol, or guessed from the
+    /// symbol name.
+    fn position_key(&self, position: &Position) -> PositionKey {
+        let category = position.category.clone().unwrap_or_else(|| {
+            self.positions
+                .keys()
+                .find(|k| k.symbol == position.symbol && k.position_idx == position.position_idx)
+                .map(|k| k.category.clone())
+                .unwrap_or_else(|| guess_category(position.symbol))
+        });
+        PositionKey::new(category, position.symbol, position.position_idx)
+    }
+}
+
+/// Options have four dash-separated parts, e.g. `BTC-30DEC22-18000-C`. Inverse contracts
+/// are quoted in USD, e.g. `BTCUSD` or `BTCUSDH25`.
+fn guess_category(symbol: &str) -> Category {
+    if symbol.split('-').count() == 4 {
+        return Category::Option;
+    }
+    // Strip the expiry of inverse futures, e.g. `H25`.
+    let quote = match symbol.len().checked_sub(3) {
+        Some(n)
+            if !symbol.contains('-')
+                && symbol[n..].starts_with(|c: char| c.is_ascii_uppercase())
+                && symbol[n + 1..].bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            &symbol[..n]
+        }
+        _ => symbol,
+    };
+    if quote.ends_with("USD") {
+        Category::Inverse
+    } else {
+        Category::Linear
+    }
+}
+
+/// A change of [`AccountState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountEvent {
+    /// A position was updated, opened or closed.
+    Position(PositionKey),
+    /// The wallet was updated.
+    Wallet,
+}
+
+/// Positions and wallet balances of an account.
+///
+/// Seed it from REST with [`AccountState::seed_positions`] and [`AccountState::seed_wallet`],
+/// then keep it up to date with the `position` and `wallet` topics of
+/// [`PrivateWebsocketApiClient`](crate::ws::private::PrivateWebsocketApiClient).
+/// Stale position updates are ignored by `updated_time`. Every clone reads and writes the
+/// same snapshot, so the private channel callback can own one while readers hold others.
+#[derive(Clone)]
+pub struct AccountState {
+    inner: Arc<RwLock<AccountSnapshot>>,
+    events: broadcast::Sender<AccountEvent>,
+}
+
+impl Default for AccountState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountState {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::default(),
+            events: broadcast::channel(256).0,
+        }
+    }
+
+    /// Replace the positions of `category` with the REST snapshot, except the ones which
+    /// were updated later by the private topic. `settle_coin` is required for `linear`
+    /// and `inverse`, e.g. `USDT`.
+    pub async fn seed_positions(
+        &self,
+        client: &RestApiClient,
+        category: Category,
+        settle_coin: Option<&str>,
+    ) -> Result<()> {
+        // Positions updated after the request may be missing from the snapshot.
+        let time = from_millis(client.clock().timestamp() as u64).unwrap_or_default();
+        let positions = client.positions(category.clone(), settle_coin).await?;
+        self.seed_positions_from(category, &positions, time);
+        Ok(())
+    }
+
+    /// Replace the wallet with the REST snapshot of `account_type`, e.g. `UNIFIED`.
+    pub async fn seed_wallet(&self, client: &RestApiClient, account_type: &str) -> Result<()> {
+        if let Some(wallet) = client.wallet_balance(account_type).await?.first() {
+            self.seed_wallet_from(wallet);
+        }
+        Ok(())
+    }
+
+    /// Apply a snapshot taken at `time`. Tracked positions missing from it are closed,
+    /// unless they were updated after `time`. Positions of the snapshot closed by the
+    /// private topic in the meantime are not reopened.
+    fn seed_positions_from(&self, category: Category, positions: &[PositionInfo], time: Timestamp) {
+        let mut inner = self.inner.write().unwrap();
+        let seeded: HashMap<_, _> = positions
+            .iter()
+            .map(|p| {
+                let key = PositionKey::new(category.clone(), &p.symbol, p.position_idx);
+                (key, PositionState::from_rest(p))
+            })
+            .collect();
+        let mut changed: Vec<PositionKey> = inner
+            .positions
+            .iter()
+            .filter(|(k, p)| {
+                k.category == category
+                    && !seeded.contains_key(k)
+                    && p.updated_time.to_millis() <= time.to_millis()
+            })
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in &changed {
+            inner.close_position(key.clone(), time);
+        }
+        for (key, position) in seeded {
+            if inner.update_position(key.clone(), position) {
+                changed.push(key);
+            }
+        }
+        drop(inner);
+        for key in changed {
+            let _ = self.events.send(AccountEvent::Position(key));
+        }
+    }
+
+    fn seed_wallet_from(&self, wallet: &WalletBalance) {
+        let mut inner = self.inner.write().unwrap();
+        inner.summary = Some(AccountSummary::from_rest(wallet));
+        inner.coins = wallet
+            .coin
+            .iter()
+            .map(|c| (c.coin.clone(), CoinBalance::from_rest(c)))
+            .collect();
+        drop(inner);
+        let _ = self.events.send(AccountEvent::Wallet);
+    }
+
+    /// Update positions and balances from a message of the private channel, e.g. in its
+    /// callback. Messages of other topics leave the state as it is.
+    pub fn apply(&self, res: &PrivateResponse) {
+        match res {
+            PrivateResponse::Position(res) => res.data.iter().for_each(|p| self.on_position(p)),
+            PrivateResponse::Wallet(res) => res.data.iter().for_each(|w| self.on_wallet(w)),
+            _ => {}
+        }
+    }
+
+    /// Apply a position update. A position of zero size is removed.
+    pub fn on_position(&self, position: &Position) {
+        let mut inner = self.inner.write().unwrap();
+        let key = inner.position_key(position);
+        let updated = inner.update_position(key.clone(), PositionState::from_ws(position));
+        drop(inner);
+        if updated {
+            let _ = self.events.send(AccountEvent::Position(key));
+        }
+    }
+
+    /// Apply a wallet update. Coins absent from the update keep their last balance.
+    pub fn on_wallet(&self, wallet: &Wallet) {
+        let mut inner = self.inner.write().unwrap();
+        inner.summary = Some(AccountSummary::from_ws(wallet));
+        for coin in &wallet.coin {
+            inner
+                .coins
+                .insert(coin.coin.to_owned(), CoinBalance::from_ws(coin));
+        }
+        drop(inner);
+        let _ = self.events.send(AccountEvent::Wallet);
+    }
+
+    /// A copy of the whole state taken under one lock.
+    pub fn snapshot(&self) -> AccountSnapshot {
+        self.inner.read().unwrap().clone()
+    }
+
+    pub fn summary(&self) -> Option<AccountSummary> {
+        self.inner.read().unwrap().summary.clone()
+    }
+
+    pub fn coin(&self, coin: &str) -> Option<CoinBalance> {
+        self.inner.read().unwrap().coins.get(coin).cloned()
+    }
+
+    pub fn position(&self, key: &PositionKey) -> Option<PositionState> {
+        self.inner.read().unwrap().positions.get(key).cloned()
+    }
+
+    /// Receive an event after each change. A lagging receiver misses the oldest events,
+    /// so read the state again with [`AccountState::snapshot`] on
+    /// [`broadcast::error::RecvError::Lagged`].
+    pub fn subscribe(&self) -> broadcast::Receiver<AccountEvent> {
+        self.events.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSITION: &str = r#"{"id":"1","topic":"position","creationTime":1697682317044,"data":[{"positionIdx":0,"tradeMode":0,"riskId":1,"riskLimitValue":"2000000","symbol":"BTCUSDT","side":"Buy","size":"0.1","entryPrice":"28000","leverage":"10","positionValue":"2800","positionBalance":"0","markPrice":"28184.5","positionIM":"280","positionMM":"14","takeProfit":"0","stopLoss":"0","trailingStop":"0","unrealisedPnl":"18.45","cumRealisedPnl":"-25.06579337","createdTime":"1694402496913","updatedTime":"1697682317038","tpslMode":"Full","liqPrice":"","bustPrice":"","positionStatus":"Normal","adlRankIndicator":0,"autoAddMargin":0,"leverageSysUpdatedTime":"","mmrSysUpdatedTime":"","seq":8327597863,"isReduceOnly":false}]}"#;
+
+    const WALLET: &str = r#"{"id":"2","topic":"wallet","creationTime":1700034722104,"data":[{"accountIMRate":"0.0112","accountMMRate":"0.0006","totalEquity":"10262.91","totalWalletBalance":"9684.46","totalMarginBalance":"9684.46","totalAvailableBalance":"9556.61","totalPerpUPL":"0","totalInitialMargin":"0","totalMaintenanceMargin":"0","coin":[{"coin":"USDT","equity":"9684.46","usdValue":"9684.46","walletBalance":"9684.46","availableToWithdraw":"","availableToBorrow":"","borrowAmount":"","accruedInterest":"","totalOrderIM":"0","totalPositionIM":"0","totalPositionMM":"0","unrealisedPnl":"0","cumRealisedPnl":"-25.06","bonus":"0","collateralSwitch":true,"marginCollateral":true,"locked":"0","spotHedgingQty":"0"}],"accountLTV":"0","accountType":"UNIFIED"}]}"#;
+
+    #[test]
+    fn test_account_state() {
+        let state = AccountState::new();
+        let mut events = state.subscribe();
+        let info: PositionInfo = serde_json::from_str(r#"{"positionIdx":0,"symbol":"BTCUSDT","side":"Buy","size":"0.1","avgPrice":"28000","positionValue":"2800","leverage":"10","markPrice":"28100","liqPrice":"","positionIM":"280","positionMM":"14","unrealisedPnl":"10","cumRealisedPnl":"-25","createdTime":"1694402496913","updatedTime":"1697682317000"}"#).unwrap();
+        state.seed_positions_from(
+            Category::Linear,
+            std::slice::from_ref(&info),
+            from_millis(1697682317001).unwrap(),
+        );
+        let key = PositionKey::new(Category::Linear, "BTCUSDT", 0);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            AccountEvent::Position(key.clone())
+        );
+
+        // The update without a category applies to the seeded position.
+        state.apply(&serde_json::from_str(POSITION).unwrap());
+        // Stale.
+        let stale = POSITION
+            .replace(r#""unrealisedPnl":"18.45""#, r#""unrealisedPnl":"12""#)
+            .replace("1697682317038", "1697682317010");
+        state.apply(&serde_json::from_str(&stale).unwrap());
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.positions.len(), 1);
+        assert_eq!(snapshot.unrealised_pnl(), 18.45);
+        assert_eq!(snapshot.positions[&key].liq_price, None);
+        assert_eq!(snapshot.positions[&key].side, Some(Side::Buy));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            AccountEvent::Position(key.clone())
+        );
+        assert!(events.try_recv().is_err());
+
+        state.apply(&serde_json::from_str(WALLET).unwrap());
+        let summary = state.summary().unwrap();
+        assert_eq!(summary.equity, Some(10262.91));
+        assert_eq!(summary.im_rate, Some(0.0112));
+        assert_eq!(state.coin("USDT").unwrap().wallet_balance, 9684.46);
+        assert_eq!(events.try_recv().unwrap(), AccountEvent::Wallet);
+
+        // A snapshot taken before the last update does not close the position.
+        state.seed_positions_from(Category::Linear, &[], from_millis(1697682317020).unwrap());
+        assert!(state.position(&key).is_some());
+        assert!(events.try_recv().is_err());
+
+        let closed = POSITION
+            .replace(r#""size":"0.1""#, r#""size":"0""#)
+            .replace("1697682317038", "1697682318000");
+        state.apply(&serde_json::from_str(&closed).unwrap());
+        assert!(state.position(&key).is_none());
+        assert_eq!(
+            events.try_recv().unwrap(),
+            AccountEvent::Position(key.clone())
+        );
+
+        // A stale update does not reopen the closed position.
+        state.apply(
+            &serde_json::from_str(&stale.replace("1697682317010", "1697682317500")).unwrap(),
+        );
+        assert!(state.position(&key).is_none());
+        // Nor does a snapshot requested before the close.
+        state.seed_positions_from(
+            Category::Linear,
+            &[info],
+            from_millis(1697682317900).unwrap(),
+        );
+        assert!(state.position(&key).is_none());
+        assert!(events.try_recv().is_err());
+
+        // An empty position in the snapshot changes nothing.
+        let empty: PositionInfo = serde_json::from_str(r#"{"positionIdx":0,"symbol":"ETHUSDT","side":"","size":"0","avgPrice":"0","positionValue":"0","leverage":"10","markPrice":"1800","liqPrice":"","positionIM":"0","positionMM":"0","unrealisedPnl":"0","cumRealisedPnl":"0","createdTime":"1694402496913","updatedTime":"1697682319000"}"#).unwrap();
+        assert_eq!(empty.side, None);
+        state.seed_positions_from(
+            Category::Linear,
+            &[empty],
+            from_millis(1697682320000).unwrap(),
+        );
+        assert!(state.snapshot().positions.is_empty());
+        assert!(events.try_recv().is_err());
+
+        assert_eq!(guess_category("BTCUSDH25"), Category::Inverse);
+        assert_eq!(guess_category("BTC-30DEC22-18000-C"), Category::Option);
+        assert_eq!(guess_category("BTC-26DEC25"), Category::Linear);
+    }
+}
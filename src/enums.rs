@@ -0,0 +1,335 @@
+//! Typed enums of the string fields in requests and responses.
+//!
+//! Every enum has an `Unknown(String)` variant, so a value added by Bybit
+//! later does not break deserialization.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$vmeta:meta])* $variant:ident => $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)+
+            /// A value unknown to this version of the crate.
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(s) => s,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    _ => Self::Unknown(value.to_owned()),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(StrVisitor::<Self>(PhantomData))
+            }
+        }
+    };
+}
+
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: for<'a> From<&'a str>> Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        Ok(T::from(v))
+    }
+}
+
+struct OptionStrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: for<'a> From<&'a str>> Visitor<'de> for OptionStrVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Option<T>, E> {
+        Ok((!v.is_empty()).then(|| T::from(v)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+}
+
+/// (De)serialize an empty string or null as `None`, e.g. `stopOrderType` of normal orders.
+/// `None` is serialized as an empty string.
+pub(crate) mod empty_as_none {
+    use super::*;
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        match value {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: for<'a> From<&'a str>,
+    {
+        deserializer.deserialize_any(OptionStrVisitor(PhantomData))
+    }
+}
+
+string_enum! {
+    /// Product type.
+    pub enum Category {
+        Spot => "spot",
+        Linear => "linear",
+        Inverse => "inverse",
+        Option => "option",
+    }
+}
+
+string_enum! {
+    /// Order or position side.
+    pub enum Side {
+        Buy => "Buy",
+        Sell => "Sell",
+    }
+}
+
+string_enum! {
+    /// Order type.
+    pub enum OrderType {
+        Market => "Market",
+        Limit => "Limit",
+    }
+}
+
+string_enum! {
+    /// Order status.
+    pub enum OrderStatus {
+        /// Placed successfully.
+        New => "New",
+        PartiallyFilled => "PartiallyFilled",
+        /// Conditional order created.
+        Untriggered => "Untriggered",
+        Rejected => "Rejected",
+        /// Spot limit order partially filled and cancelled.
+        PartiallyFilledCanceled => "PartiallyFilledCanceled",
+        Filled => "Filled",
+        Cancelled => "Cancelled",
+        /// Conditional order triggered and converted to a normal order.
+        Triggered => "Triggered",
+        /// TP/SL and conditional orders cancelled before triggered.
+        Deactivated => "Deactivated",
+    }
+}
+
+impl OrderStatus {
+    /// Whether the order can still be filled.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            Self::New | Self::PartiallyFilled | Self::Untriggered | Self::Triggered
+        )
+    }
+
+    /// Whether the order reached a final status.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Rejected
+                | Self::PartiallyFilledCanceled
+                | Self::Filled
+                | Self::Cancelled
+                | Self::Deactivated
+        )
+    }
+}
+
+string_enum! {
+    /// Time in force.
+    pub enum TimeInForce {
+        /// Good till cancel.
+        Gtc => "GTC",
+        /// Immediate or cancel.
+        Ioc => "IOC",
+        /// Fill or kill.
+        Fok => "FOK",
+        PostOnly => "PostOnly",
+        /// Retail price improvement.
+        Rpi => "RPI",
+    }
+}
+
+string_enum! {
+    /// Stop order type.
+    pub enum StopOrderType {
+        TakeProfit => "TakeProfit",
+        StopLoss => "StopLoss",
+        TrailingStop => "TrailingStop",
+        Stop => "Stop",
+        PartialTakeProfit => "PartialTakeProfit",
+        PartialStopLoss => "PartialStopLoss",
+        /// Spot TP/SL order.
+        TpslOrder => "tpslOrder",
+        /// Spot OCO order.
+        OcoOrder => "OcoOrder",
+        /// Spot margin close order on MMR.
+        MmRateClose => "MmRateClose",
+        /// Spot bidirectional TP/SL order.
+        BidirectionalTpslOrder => "BidirectionalTpslOrder",
+    }
+}
+
+string_enum! {
+    /// Execution type.
+    pub enum ExecType {
+        Trade => "Trade",
+        /// Auto-deleveraging.
+        AdlTrade => "AdlTrade",
+        Funding => "Funding",
+        /// Takeover liquidation.
+        BustTrade => "BustTrade",
+        /// USDC futures delivery.
+        Delivery => "Delivery",
+        /// Option settlement.
+        Settle => "Settle",
+        BlockTrade => "BlockTrade",
+        MovePosition => "MovePosition",
+    }
+}
+
+string_enum! {
+    /// Cancel type.
+    pub enum CancelType {
+        /// The order is not cancelled.
+        NotCancelled => "UNKNOWN",
+        CancelByUser => "CancelByUser",
+        CancelByReduceOnly => "CancelByReduceOnly",
+        CancelByPrepareLiq => "CancelByPrepareLiq",
+        CancelAllBeforeLiq => "CancelAllBeforeLiq",
+        CancelByPrepareAdl => "CancelByPrepareAdl",
+        CancelAllBeforeAdl => "CancelAllBeforeAdl",
+        CancelByAdmin => "CancelByAdmin",
+        CancelBySettle => "CancelBySettle",
+        CancelByTpSlTsClear => "CancelByTpSlTsClear",
+        CancelBySmp => "CancelBySmp",
+        /// Cancelled by DCP (disconnect-cancel-all) after the connections were lost.
+        CancelByDCP => "CancelByDCP",
+        CancelByRebalance => "CancelByRebalance",
+        /// The TP order of an OCO pair cancelled as its SL was triggered.
+        CancelByOCOTpCanceledBySlTriggered => "CancelByOCOTpCanceledBySlTriggered",
+        /// The SL order of an OCO pair cancelled as its TP was triggered.
+        CancelByOCOSlCanceledByTpTriggered => "CancelByOCOSlCanceledByTpTriggered",
+    }
+}
+
+string_enum! {
+    /// TP/SL mode.
+    pub enum TpslMode {
+        /// The entire position for TP/SL.
+        Full => "Full",
+        /// Partial position TP/SL.
+        Partial => "Partial",
+    }
+}
+
+string_enum! {
+    /// Position status.
+    pub enum PositionStatus {
+        Normal => "Normal",
+        /// In the liquidation progress.
+        Liq => "Liq",
+        /// In the auto-deleverage progress.
+        Adl => "Adl",
+    }
+}
+
+string_enum! {
+    /// The price type to trigger conditional and TP/SL orders.
+    pub enum TriggerBy {
+        LastPrice => "LastPrice",
+        IndexPrice => "IndexPrice",
+        MarkPrice => "MarkPrice",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Fields {
+        side: Side,
+        #[serde(with = "empty_as_none")]
+        stop_order_type: Option<StopOrderType>,
+        #[serde(with = "empty_as_none")]
+        trigger_by: Option<TriggerBy>,
+    }
+
+    #[test]
+    fn test_string_enum() {
+        let fields: Fields =
+            serde_json::from_str(r#"{"side":"Sell","stop_order_type":"","trigger_by":"Unseen"}"#)
+                .unwrap();
+        assert_eq!(fields.side, Side::Sell);
+        assert_eq!(fields.stop_order_type, None);
+        assert_eq!(
+            fields.trigger_by,
+            Some(TriggerBy::Unknown("Unseen".to_owned()))
+        );
+
+        assert_eq!(TimeInForce::from("PostOnly"), TimeInForce::PostOnly);
+        assert_eq!(
+            serde_json::to_string(&TimeInForce::Gtc).unwrap(),
+            r#""GTC""#
+        );
+        assert_eq!(
+            Category::Unknown("combination".to_owned()).as_str(),
+            "combination"
+        );
+        assert_eq!(CancelType::from("UNKNOWN"), CancelType::NotCancelled);
+        assert_eq!(CancelType::from("CancelByDCP"), CancelType::CancelByDCP);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::Category;
+
+/// The Bybit environment to connect to.
+///
+/// Every websocket and REST builder derives its URI from the environment,
+/// so the channel always matches the api client kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    /// bybit.com.
+    #[default]
+    Mainnet,
+    /// Testnet, bybit.com with fake money and fake market data.
+    Testnet,
+    /// Demo trading, real market data with fake money.
+    /// Public streams are the mainnet ones.
+    Demo,
+    /// Bybit Netherlands, bybit.nl.
+    Netherlands,
+    /// Bybit Kazakhstan, bybit.kz.
+    Kazakhstan,
+    /// Bybit Turkey, bybit-tr.com.
+    Turkey,
+    /// The alternative mainnet domain bytick.com.
+    Bytick,
+}
+
+impl Environment {
+    fn stream_host(&self) -> &'static str {
+        use Environment::*;
+        match self {
+            Mainnet => "stream.bybit.com",
+            Testnet => "stream-testnet.bybit.com",
+            Demo => "stream-demo.bybit.com",
+            Netherlands => "stream.bybit.nl",
+            Kazakhstan => "stream.bybit.kz",
+            Turkey => "stream.bybit-tr.com",
+            Bytick => "stream.bytick.com",
+        }
+    }
+
+    fn api_host(&self) -> &'static str {
+        use Environment::*;
+        match self {
+            Mainnet => "api.bybit.com",
+            Testnet => "api-testnet.bybit.com",
+            Demo => "api-demo.bybit.com",
+            Netherlands => "api.bybit.nl",
+            Kazakhstan => "api.bybit.kz",
+            Turkey => "api.bybit-tr.com",
+            Bytick => "api.bytick.com",
+        }
+    }
+
+    /// The public websocket channel URI of `category`.
+    pub fn public_ws(&self, category: Category) -> String {
+        let host = match self {
+            Environment::Demo => Environment::Mainnet.stream_host(),
+            _ => self.stream_host(),
+        };
+        format!("wss://{}/v5/public/{}", host, category.as_str())
+    }
+
+    /// The public spread trading websocket channel URI.
+    pub fn spread_ws(&self) -> String {
+        let host = match self {
+            Environment::Demo => Environment::Mainnet.stream_host(),
+            _ => self.stream_host(),
+        };
+        format!("wss://{}/v5/public/spread", host)
+    }
+
+    /// The private websocket channel URI.
+    pub fn private_ws(&self) -> String {
+        format!("wss://{}/v5/private", self.stream_host())
+    }
+
+    /// The trade (order entry) websocket channel URI.
+    pub fn trade_ws(&self) -> String {
+        format!("wss://{}/v5/trade", self.stream_host())
+    }
+
+    /// The REST api base URI.
+    pub fn rest(&self) -> String {
+        format!("https://{}", self.api_host())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uris() {
+        let env = Environment::Mainnet;
+        assert_eq!(
+            env.public_ws(Category::Linear),
+            "wss://stream.bybit.com/v5/public/linear"
+        );
+        assert_eq!(env.private_ws(), "wss://stream.bybit.com/v5/private");
+        assert_eq!(env.trade_ws(), "wss://stream.bybit.com/v5/trade");
+        assert_eq!(env.spread_ws(), "wss://stream.bybit.com/v5/public/spread");
+        assert_eq!(env.rest(), "https://api.bybit.com");
+
+        let env = Environment::Testnet;
+        assert_eq!(
+            env.public_ws(Category::Option),
+            "wss://stream-testnet.bybit.com/v5/public/option"
+        );
+        assert_eq!(env.rest(), "https://api-testnet.bybit.com");
+
+        let env = Environment::Demo;
+        assert_eq!(
+            env.public_ws(Category::Spot),
+            "wss://stream.bybit.com/v5/public/spot"
+        );
+        assert_eq!(env.private_ws(), "wss://stream-demo.bybit.com/v5/private");
+        assert_eq!(env.rest(), "https://api-demo.bybit.com");
+
+        let env = Environment::Turkey;
+        assert_eq!(
+            env.public_ws(Category::Inverse),
+            "wss://stream.bybit-tr.com/v5/public/inverse"
+        );
+        assert_eq!(env.rest(), "https://api.bybit-tr.com");
+    }
+}
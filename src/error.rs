@@ -1,4 +1,6 @@
+use reqwest::Error as ReqwestError;
 use serde_json::error::Error as SerdeError;
+use serde_qs::Error as QsError;
 use std::result;
 use thiserror::Error;
 use tungstenite::error::Error as TungsteniteError;
@@ -11,5 +13,61 @@ pub enum BybitError {
     SerdeError(#[from] SerdeError),
 
     #[error("Tungstenite error: {0}")]
-    TungsteniteError(#[from] TungsteniteError),
+    TungsteniteError(Box<TungsteniteError>),
+
+    #[error("Reqwest error: {0}")]
+    ReqwestError(#[from] ReqwestError),
+
+    #[error("Query string error: {0}")]
+    QsError(#[from] QsError),
+
+    #[error("Api error: {code} {msg}")]
+    ApiError { code: i32, msg: String },
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Invalid symbol: {0}")]
+    InvalidSymbol(String),
+
+    #[error("Invalid interval: {0}")]
+    InvalidInterval(String),
+
+    #[error("Invalid category: {0}")]
+    InvalidCategory(String),
+
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    #[error("Credentials are required")]
+    MissingCredentials,
+
+    #[error("Request timed out")]
+    Timeout,
+
+    #[error("Connection closed")]
+    ConnectionClosed,
+
+    #[error("Orderbook of {0} is not synced")]
+    OrderbookNotSynced(String),
+
+    #[error("Orderbook gap of {symbol}: expected update {expected}, received {received}")]
+    OrderbookGap {
+        symbol: String,
+        expected: u64,
+        received: u64,
+    },
+
+    #[error("Orderbook sequence of {symbol} does not increase: last {last}, received {received}")]
+    OrderbookSequence {
+        symbol: String,
+        last: u64,
+        received: u64,
+    },
+}
+
+impl From<TungsteniteError> for BybitError {
+    fn from(value: TungsteniteError) -> Self {
+        Self::TungsteniteError(Box::new(value))
+    }
 }
@@ -0,0 +1,589 @@
+//! Candles aggregated from trades, or resampled from 1-minute klines.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::error::{BybitError, Result};
+use crate::num::ToF64;
+use crate::rest::market::MarketKline;
+use crate::rest::RestApiClient;
+use crate::timestamp::{from_millis, Timestamp, ToMillis};
+use crate::ws::response::{Kline, Trade};
+use crate::{Category, KlineInterval};
+
+/// Weekly candles start on Monday, 4 days after the epoch.
+const WEEK_OFFSET: u64 = 4 * 24 * 60 * 60 * 1000;
+
+const MINUTE: u64 = 60 * 1000;
+
+/// An OHLCV candle built by [`KlineAggregator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    /// The start timestamp.
+    pub start: Timestamp,
+    /// The end timestamp, i.e. one millisecond before the start of the next candle.
+    pub end: Timestamp,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub turnover: f64,
+    /// Whether the candle is closed.
+    pub confirm: bool,
+}
+
+impl Candle {
+    fn new(start: u64, period: u64, price: f64) -> Self {
+        Self {
+            start: millis(start),
+            end: millis(start + period - 1),
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+            turnover: 0.0,
+            confirm: false,
+        }
+    }
+}
+
+impl TryFrom<&Kline<'_>> for Candle {
+    type Error = BybitError;
+
+    fn try_from(kline: &Kline) -> Result<Self> {
+        let err = || BybitError::InvalidResponse(format!("invalid kline: {:?}", kline));
+        Ok(Self {
+            start: kline.start,
+            end: kline.end,
+            open: kline.open.to_f64().ok_or_else(err)?,
+            high: kline.high.to_f64().ok_or_else(err)?,
+            low: kline.low.to_f64().ok_or_else(err)?,
+            close: kline.close.to_f64().ok_or_else(err)?,
+            volume: kline.volume.to_f64().unwrap_or_default(),
+            turnover: kline.turnover.to_f64().unwrap_or_default(),
+            confirm: kline.confirm,
+        })
+    }
+}
+
+fn millis(ms: u64) -> Timestamp {
+    from_millis(ms).unwrap_or_default()
+}
+
+/// Build candles of a fixed duration from the `publicTrade` stream, or resample confirmed
+/// 1-minute klines of the `kline` stream into a higher interval.
+///
+/// Feed an aggregator with either trades or klines, not both.
+#[derive(Debug, Clone)]
+pub struct KlineAggregator {
+    period: u64,
+    offset: u64,
+    current: Option<Candle>,
+    /// The start of the last 1-minute kline added.
+    last_minute: Option<u64>,
+    /// Whether the current candle misses some of its 1-minute klines.
+    partial: bool,
+}
+
+impl KlineAggregator {
+    /// Create an aggregator of `interval`, aligned like the `kline` topic.
+    /// `Month` is not supported.
+    pub fn new(interval: KlineInterval) -> Result<Self> {
+        let period = interval
+            .duration()
+            .ok_or_else(|| BybitError::InvalidInterval(<&str>::from(interval).to_owned()))?;
+        let mut aggregator = Self::with_duration(period)?;
+        if period == Duration::from_secs(7 * 24 * 60 * 60) {
+            aggregator.offset = WEEK_OFFSET;
+        }
+        Ok(aggregator)
+    }
+
+    /// Create an aggregator of an arbitrary duration, e.g. 10 seconds. Candles are aligned
+    /// to multiples of `duration` since the epoch. The duration must be a positive number
+    /// of milliseconds.
+    pub fn with_duration(duration: Duration) -> Result<Self> {
+        let period = duration.as_millis() as u64;
+        if period == 0 {
+            return Err(BybitError::InvalidInterval(format!("{:?}", duration)));
+        }
+        Ok(Self {
+            period,
+            offset: 0,
+            current: None,
+            last_minute: None,
+            partial: false,
+        })
+    }
+
+    /// The candle being built.
+    pub fn current(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// Add a trade. Return the previous candle, closed, if the trade starts a new one.
+    /// Trades older than the current candle are ignored.
+    ///
+    /// The first candle starts with the first trade added, usually mid-candle, so its
+    /// OHLCV only covers the trades since then.
+    pub fn add_trade(&mut self, trade: &Trade) -> Option<Candle> {
+        let (Some(price), Some(size)) = (trade.p.to_f64(), trade.v.to_f64()) else {
+            return None;
+        };
+        let start = self.align(trade.T.to_millis());
+        let closed = self.roll(start, price);
+        if let Some(candle) = self
+            .current
+            .as_mut()
+            .filter(|c| c.start.to_millis() == start)
+        {
+            candle.high = candle.high.max(price);
+            candle.low = candle.low.min(price);
+            candle.close = price;
+            candle.volume += size;
+            candle.turnover += price * size;
+        }
+        closed
+    }
+
+    /// Add a 1-minute kline. Unconfirmed klines, and replayed ones at or before the last
+    /// added minute, are ignored.
+    /// Return the candle, closed, once its last minute or a later kline is added. Candles
+    /// missing any of their minutes, e.g. the first one when started mid-candle, are never
+    /// returned.
+    ///
+    /// Fail with `InvalidInterval` if the kline is not a 1-minute one.
+    pub fn add_kline(&mut self, kline: &Kline) -> Result<Option<Candle>> {
+        if kline.interval != <&str>::from(KlineInterval::Min1) {
+            return Err(BybitError::InvalidInterval(kline.interval.to_owned()));
+        }
+        Ok(self.resample(kline))
+    }
+
+    fn resample(&mut self, kline: &Kline) -> Option<Candle> {
+        if !kline.confirm {
+            return None;
+        }
+        let (Some(open), Some(high), Some(low), Some(close)) = (
+            kline.open.to_f64(),
+            kline.high.to_f64(),
+            kline.low.to_f64(),
+            kline.close.to_f64(),
+        ) else {
+            return None;
+        };
+        let minute = kline.start.to_millis();
+        if self.last_minute.is_some_and(|last| minute <= last) {
+            return None;
+        }
+        let start = self.align(minute);
+        let expected = match (&self.current, self.last_minute) {
+            (Some(candle), Some(last)) if candle.start.to_millis() == start => last + MINUTE,
+            _ => start,
+        };
+        let closed = self.roll(start, open);
+        self.last_minute = Some(minute);
+        self.partial |= minute != expected;
+        let candle = self
+            .current
+            .as_mut()
+            .filter(|c| c.start.to_millis() == start)?;
+        candle.high = candle.high.max(high);
+        candle.low = candle.low.min(low);
+        candle.close = close;
+        candle.volume += kline.volume.to_f64().unwrap_or_default();
+        candle.turnover += kline.turnover.to_f64().unwrap_or_default();
+        if kline.end.to_millis() >= candle.end.to_millis() {
+            return closed.or_else(|| self.close());
+        }
+        closed
+    }
+
+    /// Close the current candle if `now` is past its end, e.g. when no trade arrives.
+    pub fn close_until(&mut self, now: Timestamp) -> Option<Candle> {
+        if self.current.as_ref()?.end.to_millis() < now.to_millis() {
+            return self.close();
+        }
+        None
+    }
+
+    fn close(&mut self) -> Option<Candle> {
+        let mut candle = self.current.take()?;
+        // Resampled candles also miss their last minutes if a later candle closes them.
+        let complete = self
+            .last_minute
+            .is_none_or(|last| last + MINUTE == candle.end.to_millis() + 1);
+        if std::mem::take(&mut self.partial) || !complete {
+            return None;
+        }
+        candle.confirm = true;
+        Some(candle)
+    }
+
+    /// Start a new candle at `start` if it is later than the current one,
+    /// returning the current one closed.
+    fn roll(&mut self, start: u64, open: f64) -> Option<Candle> {
+        match &self.current {
+            Some(candle) if candle.start.to_millis() >= start => None,
+            _ => {
+                let closed = self.close();
+                self.current = Some(Candle::new(start, self.period, open));
+                closed
+            }
+        }
+    }
+
+    fn align(&self, ts: u64) -> u64 {
+        let ts = ts.saturating_sub(self.offset);
+        ts - ts % self.period + self.offset
+    }
+}
+
+/// A series of candles of a symbol, backfilled with the REST kline endpoint and kept up
+/// to date with the `kline.{interval}.{symbol}` topic.
+///
+/// ```ignore
+/// let mut series = CandleSeries::new(Category::Linear, "BTCUSDT", KlineInterval::Min1, 500);
+/// series.backfill(&rest_client).await?;
+/// ws_client.subscribe_kline("BTCUSDT", KlineInterval::Min1);
+/// // Then call `series.update(&kline)` with every kline received.
+/// ```
+pub struct CandleSeries {
+    category: Category,
+    symbol: String,
+    interval: KlineInterval,
+    capacity: usize,
+    confirmed_only: bool,
+    candles: BTreeMap<u64, Candle>,
+}
+
+impl CandleSeries {
+    /// Create a series which keeps the latest `capacity` candles.
+    pub fn new<S: Into<String>>(
+        category: Category,
+        symbol: S,
+        interval: KlineInterval,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            interval,
+            capacity,
+            confirmed_only: false,
+            candles: BTreeMap::new(),
+        }
+    }
+
+    /// Only return confirmed candles from [`CandleSeries::update`].
+    pub fn confirmed_only(mut self, confirmed_only: bool) -> Self {
+        self.confirmed_only = confirmed_only;
+        self
+    }
+
+    /// The topic to subscribe to, i.e. `kline.{interval}.{symbol}`.
+    pub fn topic(&self) -> String {
+        format!("kline.{}.{}", <&str>::from(self.interval), self.symbol)
+    }
+
+    /// Fetch up to `capacity` historical candles. Candles already received from the
+    /// topic are kept. Return the number of fetched candles.
+    pub async fn backfill(&mut self, client: &RestApiClient) -> Result<usize> {
+        let now = client.clock().timestamp() as u64;
+        let period = self.interval.duration().map(|d| d.as_millis() as u64);
+        let mut fetched = 0;
+        let mut end = None;
+        while fetched < self.capacity {
+            let limit = (self.capacity - fetched).min(1000) as u16;
+            let page = client
+                .klines(
+                    self.category.clone(),
+                    &self.symbol,
+                    self.interval,
+                    None,
+                    end,
+                    limit,
+                )
+                .await?;
+            let Some(oldest) = page.last() else {
+                break;
+            };
+            end = Some(oldest.start.to_millis().saturating_sub(1));
+            fetched += page.len();
+            let len = page.len();
+            for kline in &page {
+                let candle = self.historical(kline, period, now)?;
+                self.candles
+                    .entry(candle.start.to_millis())
+                    .or_insert(candle);
+            }
+            if len < limit as usize {
+                break;
+            }
+        }
+        self.trim();
+        Ok(fetched)
+    }
+
+    /// Apply a kline of the topic, replacing the unconfirmed candle of the same start.
+    /// Updates of a confirmed candle are ignored.
+    ///
+    /// Return the updated candle, or only confirmed ones if [`CandleSeries::confirmed_only`] is set.
+    /// Fail with `InvalidInterval` if the kline is of another interval.
+    pub fn update(&mut self, kline: &Kline) -> Result<Option<&Candle>> {
+        if kline.interval != <&str>::from(self.interval) {
+            return Err(BybitError::InvalidInterval(kline.interval.to_owned()));
+        }
+        let candle = Candle::try_from(kline)?;
+        let start = candle.start.to_millis();
+        if self.candles.get(&start).is_some_and(|c| c.confirm) {
+            return Ok(None);
+        }
+        let oldest = self.candles.keys().next().copied();
+        if self.candles.len() >= self.capacity && oldest.is_none_or(|o| start < o) {
+            return Ok(None);
+        }
+        let confirm = candle.confirm;
+        self.candles.insert(start, candle);
+        self.trim();
+        if self.confirmed_only && !confirm {
+            return Ok(None);
+        }
+        Ok(self.candles.get(&start))
+    }
+
+    /// Candles in ascending order of the start time.
+    pub fn candles(&self) -> impl DoubleEndedIterator<Item = &Candle> {
+        self.candles.values()
+    }
+
+    /// The latest candle, which may be unconfirmed.
+    pub fn last(&self) -> Option<&Candle> {
+        self.candles.values().next_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.candles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candles.is_empty()
+    }
+
+    fn historical(&self, kline: &MarketKline, period: Option<u64>, now: u64) -> Result<Candle> {
+        let err = || BybitError::InvalidResponse(format!("invalid kline: {:?}", kline));
+        let start = kline.start.to_millis();
+        // The duration of a month varies. Use the start of the next candle instead.
+        let end = match period {
+            Some(period) => start + period - 1,
+            None => self
+                .candles
+                .range(start + 1..)
+                .next()
+                .map_or(u64::MAX, |(next, _)| next - 1),
+        };
+        Ok(Candle {
+            start: kline.start,
+            end: millis(end.min(now.max(start))),
+            open: kline.open.to_f64().ok_or_else(err)?,
+            high: kline.high.to_f64().ok_or_else(err)?,
+            low: kline.low.to_f64().ok_or_else(err)?,
+            close: kline.close.to_f64().ok_or_else(err)?,
+            volume: kline.volume.to_f64().ok_or_else(err)?,
+            turnover: kline.turnover.to_f64().ok_or_else(err)?,
+            confirm: end < now,
+        })
+    }
+
+    fn trim(&mut self) {
+        while self.candles.len() > self.capacity {
+            self.candles.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(ts: u64, price: &str, size: &str) -> String {
+        format!(
+            r#"{{"T":{ts},"s":"BTCUSDT","S":"Buy","v":"{size}","p":"{price}","L":"PlusTick","i":"1","BT":false}}"#
+        )
+    }
+
+    fn kline(start: u64, open: &str, close: &str, confirm: bool) -> String {
+        format!(
+            r#"{{"start":{start},"end":{},"interval":"1","open":"{open}","close":"{close}","high":"{close}","low":"{open}","volume":"2","turnover":"100","confirm":{confirm},"timestamp":{}}}"#,
+            start + 59_999,
+            start + 59_999
+        )
+    }
+
+    #[test]
+    fn test_trades() {
+        let mut aggregator = KlineAggregator::with_duration(Duration::from_secs(10)).unwrap();
+        let trades = [
+            trade(1_700_000_001_000, "100", "1"),
+            trade(1_700_000_005_000, "102", "2"),
+            trade(1_700_000_009_999, "99", "1"),
+            trade(1_700_000_012_000, "101", "1"),
+        ];
+        let closed: Vec<Candle> = trades
+            .iter()
+            .filter_map(|t| aggregator.add_trade(&serde_json::from_str(t).unwrap()))
+            .collect();
+        assert_eq!(closed.len(), 1);
+        let candle = &closed[0];
+        assert_eq!(candle.start.to_millis(), 1_700_000_000_000);
+        assert_eq!(candle.end.to_millis(), 1_700_000_009_999);
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.close),
+            (100.0, 102.0, 99.0, 99.0)
+        );
+        assert_eq!(candle.volume, 4.0);
+        assert_eq!(candle.turnover, 100.0 + 204.0 + 99.0);
+        assert!(candle.confirm);
+
+        assert!(aggregator.close_until(millis(1_700_000_019_999)).is_none());
+        assert_eq!(
+            aggregator
+                .close_until(millis(1_700_000_020_000))
+                .unwrap()
+                .open,
+            101.0
+        );
+    }
+
+    #[test]
+    fn test_resample() {
+        let mut aggregator = KlineAggregator::new(KlineInterval::Min5).unwrap();
+        let start = 1_700_000_100_000;
+        assert!(aggregator
+            .add_kline(&serde_json::from_str(&kline(start, "10", "11", false)).unwrap())
+            .unwrap()
+            .is_none());
+        assert!(aggregator.current().is_none());
+        for i in 0..4 {
+            let k = kline(start + i * 60_000, "10", "11", true);
+            assert!(aggregator
+                .add_kline(&serde_json::from_str(&k).unwrap())
+                .unwrap()
+                .is_none());
+        }
+        let k = kline(start + 4 * 60_000, "11", "12", true);
+        let candle = aggregator
+            .add_kline(&serde_json::from_str(&k).unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(candle.start.to_millis(), start);
+        assert_eq!((candle.open, candle.close), (10.0, 12.0));
+        assert_eq!(candle.volume, 10.0);
+        assert!(aggregator.current().is_none());
+
+        // Only 1-minute klines are resampled.
+        let k = kline(start + 5 * 60_000, "12", "13", true)
+            .replace(r#""interval":"1""#, r#""interval":"5""#);
+        assert!(matches!(
+            aggregator.add_kline(&serde_json::from_str(&k).unwrap()),
+            Err(BybitError::InvalidInterval(_))
+        ));
+        assert!(aggregator.current().is_none());
+
+        assert!(KlineAggregator::new(KlineInterval::Month).is_err());
+        let week = KlineAggregator::new(KlineInterval::Week).unwrap();
+        // Monday, 13 November 2023.
+        assert_eq!(week.align(1_700_000_000_000), 1_699_833_600_000);
+    }
+
+    #[test]
+    fn test_resample_replay() {
+        let mut aggregator = KlineAggregator::new(KlineInterval::Min5).unwrap();
+        let start = 1_700_000_100_000;
+        let add = |aggregator: &mut KlineAggregator, offset: u64| {
+            let k = kline(start + offset * 60_000, "10", "11", true);
+            aggregator
+                .add_kline(&serde_json::from_str(&k).unwrap())
+                .unwrap()
+        };
+
+        // Started mid-candle, the first candle misses its first minutes.
+        assert!(add(&mut aggregator, 3).is_none());
+        assert!(add(&mut aggregator, 4).is_none());
+
+        assert!(add(&mut aggregator, 5).is_none());
+        assert!(add(&mut aggregator, 6).is_none());
+        // A replayed minute adds its volume once.
+        assert!(add(&mut aggregator, 6).is_none());
+        assert!(add(&mut aggregator, 7).is_none());
+        assert!(add(&mut aggregator, 8).is_none());
+        let candle = add(&mut aggregator, 9).unwrap();
+        assert_eq!(candle.start.to_millis(), start + 5 * 60_000);
+        assert_eq!(candle.volume, 10.0);
+        // The last minute replayed after the candle closed.
+        assert!(add(&mut aggregator, 9).is_none());
+        assert!(aggregator.current().is_none());
+
+        // A minute missing in the middle.
+        assert!(add(&mut aggregator, 10).is_none());
+        assert!(add(&mut aggregator, 12).is_none());
+        assert!(add(&mut aggregator, 13).is_none());
+        assert!(add(&mut aggregator, 14).is_none());
+        assert!(add(&mut aggregator, 15).is_none());
+        assert_eq!(
+            aggregator.current().unwrap().start.to_millis(),
+            start + 15 * 60_000
+        );
+
+        // The last minute missing, the next candle closes it.
+        assert!(add(&mut aggregator, 20).is_none());
+        assert!(add(&mut aggregator, 21).is_none());
+        assert!(add(&mut aggregator, 22).is_none());
+        assert!(add(&mut aggregator, 23).is_none());
+        assert!(add(&mut aggregator, 25).is_none());
+        // Or `close_until`.
+        assert!(add(&mut aggregator, 26).is_none());
+        assert!(aggregator
+            .close_until(millis(start + 31 * 60_000))
+            .is_none());
+        assert!(aggregator.current().is_none());
+    }
+
+    #[test]
+    fn test_candle_series() {
+        let mut series = CandleSeries::new(Category::Linear, "BTCUSDT", KlineInterval::Min1, 2)
+            .confirmed_only(true);
+        assert_eq!(series.topic(), "kline.1.BTCUSDT");
+
+        let start = 1_700_000_100_000;
+        let update = |series: &mut CandleSeries, k: &str| {
+            let kline: Kline = serde_json::from_str(k).unwrap();
+            series.update(&kline).unwrap().cloned()
+        };
+        assert!(update(&mut series, &kline(start, "10", "11", false)).is_none());
+        assert_eq!(series.last().unwrap().close, 11.0);
+        assert!(update(&mut series, &kline(start, "10", "12", false)).is_none());
+        assert_eq!(series.len(), 1);
+        let candle = update(&mut series, &kline(start, "10", "13", true)).unwrap();
+        assert_eq!(candle.close, 13.0);
+        // Duplicates of a confirmed candle.
+        assert!(update(&mut series, &kline(start, "10", "14", true)).is_none());
+        assert_eq!(series.last().unwrap().close, 13.0);
+
+        update(&mut series, &kline(start + 60_000, "13", "15", false));
+        update(&mut series, &kline(start + 120_000, "15", "16", false));
+        let starts: Vec<u64> = series.candles().map(|c| c.start.to_millis()).collect();
+        assert_eq!(starts, vec![start + 60_000, start + 120_000]);
+
+        let k = kline(start + 180_000, "16", "17", true)
+            .replace(r#""interval":"1""#, r#""interval":"5""#);
+        let kline: Kline = serde_json::from_str(&k).unwrap();
+        assert!(matches!(
+            series.update(&kline),
+            Err(BybitError::InvalidInterval(_))
+        ));
+        assert_eq!(series.len(), 2);
+    }
+}
@@ -1,7 +1,27 @@
+pub mod account;
+pub mod credentials;
+pub mod enums;
+pub mod environment;
 pub mod error;
+pub mod kline;
+pub mod num;
+pub mod option;
+pub mod order;
+pub mod orderbook;
+pub mod rest;
+pub mod ticker;
+pub mod time_sync;
+pub mod timestamp;
 pub mod util;
 pub mod ws;
 
+use std::time::Duration;
+
+pub use credentials::Credentials;
+pub use enums::Category;
+pub use environment::Environment;
+pub use rest::RestApiClient;
+pub use time_sync::{Clock, TimeSync};
 pub use ws::WebSocketApiClient;
 
 pub enum FutureRole {
@@ -9,6 +29,16 @@ pub enum FutureRole {
     Inverse,
 }
 
+impl From<&FutureRole> for Category {
+    fn from(value: &FutureRole) -> Self {
+        match value {
+            FutureRole::Linear => Category::Linear,
+            FutureRole::Inverse => Category::Inverse,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum KlineInterval {
     Min1,
     Min3,
@@ -25,6 +55,29 @@ pub enum KlineInterval {
     Month,
 }
 
+impl KlineInterval {
+    /// The duration of a candle. `None` for `Month` whose duration varies.
+    pub fn duration(&self) -> Option<Duration> {
+        use KlineInterval::*;
+        let mins = match self {
+            Min1 => 1,
+            Min3 => 3,
+            Min5 => 5,
+            Min15 => 15,
+            Min30 => 30,
+            Min60 => 60,
+            Min120 => 120,
+            Min240 => 240,
+            Min360 => 360,
+            Min720 => 720,
+            Day => 1440,
+            Week => 10080,
+            Month => return None,
+        };
+        Some(Duration::from_secs(mins * 60))
+    }
+}
+
 impl From<KlineInterval> for &str {
     fn from(value: KlineInterval) -> Self {
         use KlineInterval::*;
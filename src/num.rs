@@ -0,0 +1,169 @@
+//! Numeric fields of responses.
+//!
+//! By default, prices, sizes, fees and balances are borrowed exactly as received, e.g. `"0.0123"`.
+//! With the `rust_decimal` feature, they are deserialized into [`Decimal`], and fields
+//! which can be empty into `Option<Decimal>`, keeping the exact precision of the string.
+
+#[cfg(feature = "rust_decimal")]
+pub use rust_decimal::Decimal;
+
+/// A number which is never empty.
+#[cfg(not(feature = "rust_decimal"))]
+pub type Num<'a> = &'a str;
+/// A number which is never empty.
+#[cfg(feature = "rust_decimal")]
+pub type Num<'a> = Decimal;
+
+/// A number which can be an empty string, e.g. `liqPrice` of an empty position.
+#[cfg(not(feature = "rust_decimal"))]
+pub type OptNum<'a> = &'a str;
+/// A number which can be an empty string, e.g. `liqPrice` of an empty position.
+#[cfg(feature = "rust_decimal")]
+pub type OptNum<'a> = Option<Decimal>;
+
+/// The owned counterpart of [`Num`].
+#[cfg(not(feature = "rust_decimal"))]
+pub type NumOwned = String;
+/// The owned counterpart of [`Num`].
+#[cfg(feature = "rust_decimal")]
+pub type NumOwned = Decimal;
+
+/// The owned counterpart of [`OptNum`].
+#[cfg(not(feature = "rust_decimal"))]
+pub type OptNumOwned = String;
+/// The owned counterpart of [`OptNum`].
+#[cfg(feature = "rust_decimal")]
+pub type OptNumOwned = Option<Decimal>;
+
+/// Convert a numeric field to `f64`, whichever representation is enabled.
+/// Empty and malformed values are `None`.
+pub trait ToF64 {
+    fn to_f64(&self) -> Option<f64>;
+}
+
+impl ToF64 for &str {
+    fn to_f64(&self) -> Option<f64> {
+        self.parse().ok()
+    }
+}
+
+impl ToF64 for String {
+    fn to_f64(&self) -> Option<f64> {
+        self.parse().ok()
+    }
+}
+
+impl<T: ToF64> ToF64 for Option<T> {
+    fn to_f64(&self) -> Option<f64> {
+        self.as_ref().and_then(ToF64::to_f64)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl ToF64 for Decimal {
+    fn to_f64(&self) -> Option<f64> {
+        rust_decimal::prelude::ToPrimitive::to_f64(self)
+    }
+}
+
+/// (De)serialize a [`Decimal`] which can be an empty string. An empty string, null or
+/// a missing field is `None`, and `None` is serialized as an empty string.
+#[cfg(feature = "rust_decimal")]
+pub(crate) mod opt {
+    use super::Decimal;
+    use serde::{Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(v) => Serialize::serialize(v, serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+        use std::fmt;
+        use std::str::FromStr;
+
+        struct OptVisitor;
+
+        impl<'de> Visitor<'de> for OptVisitor {
+            type Value = Option<Decimal>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a decimal string or an empty string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                if v.is_empty() {
+                    return Ok(None);
+                }
+                Decimal::from_str(v)
+                    .or_else(|_| Decimal::from_scientific(v))
+                    .map(Some)
+                    .map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(Some(v.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Some(v.into()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                Decimal::try_from(v).map(Some).map_err(E::custom)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_some<D: serde::Deserializer<'de>>(
+                self,
+                d: D,
+            ) -> Result<Self::Value, D::Error> {
+                d.deserialize_any(self)
+            }
+        }
+
+        deserializer.deserialize_any(OptVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "rust_decimal"))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::str::FromStr;
+
+    #[derive(Deserialize)]
+    struct Fields {
+        price: Decimal,
+        #[serde(with = "opt")]
+        liq_price: Option<Decimal>,
+        #[serde(default, with = "opt")]
+        balance: Option<Decimal>,
+    }
+
+    #[test]
+    fn test_opt() {
+        let fields: Fields = serde_json::from_str(r#"{"price":"0.10000","liq_price":""}"#).unwrap();
+        assert_eq!(fields.price.to_string(), "0.10000");
+        assert_eq!(fields.liq_price, None);
+        assert_eq!(fields.balance, None);
+
+        let fields: Fields =
+            serde_json::from_str(r#"{"price":"1e-8","liq_price":"25.5","balance":null}"#).unwrap();
+        assert_eq!(fields.price, Decimal::from_str("0.00000001").unwrap());
+        assert_eq!(fields.liq_price.to_f64(), Some(25.5));
+    }
+}
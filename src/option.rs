@@ -0,0 +1,359 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::BybitError;
+use crate::num::ToF64;
+use crate::timestamp::Timestamp;
+use crate::ws::response::OptionTicker;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// The expiry date of an option, e.g. `29DEC23`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Expiry {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for Expiry {
+    type Err = BybitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || BybitError::InvalidSymbol(s.to_string());
+        if !s.is_ascii() {
+            return Err(err());
+        }
+        let split = s.find(|c: char| c.is_ascii_alphabetic()).ok_or_else(err)?;
+        if split == 0 || s.len() != split + 5 {
+            return Err(err());
+        }
+        let (day, rest) = s.split_at(split);
+        let (month, year) = rest.split_at(3);
+        let month = MONTHS.iter().position(|m| *m == month).ok_or_else(err)?;
+        Ok(Self {
+            year: 2000 + year.parse::<u16>().map_err(|_| err())?,
+            month: month as u8 + 1,
+            day: day.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+impl fmt::Display for Expiry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let month = (self.month as usize)
+            .checked_sub(1)
+            .and_then(|m| MONTHS.get(m));
+        match month {
+            Some(month) => write!(f, "{}{}{:02}", self.day, month, self.year % 100),
+            // Out of range months cannot be named, keep them visible anyway.
+            None => write!(f, "{}M{}{:02}", self.day, self.month, self.year % 100),
+        }
+    }
+}
+
+/// Call or put.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+/// An option instrument parsed from its symbol, e.g. `BTC-29DEC23-40000-C` or `BTC-29DEC23-40000-C-USDT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionInstrument {
+    /// Base coin.
+    pub base: String,
+    /// Expiry date.
+    pub expiry: Expiry,
+    /// Strike price.
+    pub strike: f64,
+    /// Call or put.
+    pub kind: OptionKind,
+    /// Settle coin of USDT settled options.
+    pub settle: Option<String>,
+}
+
+impl FromStr for OptionInstrument {
+    type Err = BybitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || BybitError::InvalidSymbol(s.to_string());
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts.len() != 4 && parts.len() != 5 {
+            return Err(err());
+        }
+        let kind = match parts[3] {
+            "C" => OptionKind::Call,
+            "P" => OptionKind::Put,
+            _ => return Err(err()),
+        };
+        Ok(Self {
+            base: parts[0].to_string(),
+            expiry: parts[1].parse().map_err(|_| err())?,
+            strike: parts[2].parse().map_err(|_| err())?,
+            kind,
+            settle: parts.get(4).map(|s| s.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for OptionInstrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            OptionKind::Call => "C",
+            OptionKind::Put => "P",
+        };
+        write!(f, "{}-{}-{}-{}", self.base, self.expiry, self.strike, kind)?;
+        if let Some(settle) = &self.settle {
+            write!(f, "-{}", settle)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the instruments of `symbols` that match `filter`, skipping non-option symbols.
+pub fn filter_instruments<I, S, F>(symbols: I, filter: F) -> Vec<OptionInstrument>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    F: Fn(&OptionInstrument) -> bool,
+{
+    symbols
+        .into_iter()
+        .filter_map(|s| s.as_ref().parse().ok())
+        .filter(|i| filter(i))
+        .collect()
+}
+
+/// A point of the volatility surface, taken from an option ticker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfacePoint {
+    /// Implied volatility of mark price.
+    pub mark_iv: f64,
+    /// Implied volatility of best bid. `None` if there is no bid.
+    pub bid_iv: Option<f64>,
+    /// Implied volatility of best ask. `None` if there is no ask.
+    pub ask_iv: Option<f64>,
+    pub delta: Option<f64>,
+    pub gamma: Option<f64>,
+    pub vega: Option<f64>,
+    pub theta: Option<f64>,
+    /// Underlying price.
+    pub underlying_price: Option<f64>,
+    /// The timestamp of the ticker.
+    pub ts: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Strike(f64);
+
+impl Eq for Strike {}
+
+impl PartialOrd for Strike {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Strike {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// An in-memory volatility surface of a base coin, built from option tickers.
+#[derive(Debug)]
+pub struct VolatilitySurface {
+    base: String,
+    points: BTreeMap<(Expiry, OptionKind), BTreeMap<Strike, SurfacePoint>>,
+}
+
+impl VolatilitySurface {
+    /// Create the surface of `base`, e.g. `BTC`.
+    pub fn new<S: Into<String>>(base: S) -> Self {
+        Self {
+            base: base.into(),
+            points: BTreeMap::new(),
+        }
+    }
+
+    /// The base coin.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Update the surface with a ticker. Tickers of another base coin, or whose symbol or
+    /// mark iv can not be parsed, are ignored.
+    pub fn update(&mut self, ticker: &OptionTicker, ts: Timestamp) -> Option<OptionInstrument> {
+        let instrument: OptionInstrument = ticker.symbol.parse().ok()?;
+        if instrument.base != self.base {
+            return None;
+        }
+        let point = SurfacePoint {
+            mark_iv: ticker.mark_price_iv.to_f64()?,
+            bid_iv: positive(&ticker.bid_iv),
+            ask_iv: positive(&ticker.ask_iv),
+            delta: ticker.delta.to_f64(),
+            gamma: ticker.gamma.to_f64(),
+            vega: ticker.vega.to_f64(),
+            theta: ticker.theta.to_f64(),
+            underlying_price: ticker.underlying_price.to_f64(),
+            ts,
+        };
+        self.points
+            .entry((instrument.expiry, instrument.kind))
+            .or_default()
+            .insert(Strike(instrument.strike), point);
+        Some(instrument)
+    }
+
+    /// Remove the points of expired options.
+    pub fn remove_expiry(&mut self, expiry: Expiry) {
+        self.points.retain(|(e, _), _| *e != expiry);
+    }
+
+    /// All expiries in ascending order.
+    pub fn expiries(&self) -> Vec<Expiry> {
+        let mut expiries: Vec<Expiry> = self.points.keys().map(|(e, _)| *e).collect();
+        expiries.dedup();
+        expiries
+    }
+
+    /// The point of an instrument.
+    pub fn get(&self, expiry: Expiry, strike: f64, kind: OptionKind) -> Option<&SurfacePoint> {
+        self.points.get(&(expiry, kind))?.get(&Strike(strike))
+    }
+
+    /// The (strike, point) pairs of an expiry in ascending strike order.
+    pub fn smile(&self, expiry: Expiry, kind: OptionKind) -> Vec<(f64, &SurfacePoint)> {
+        self.points
+            .get(&(expiry, kind))
+            .map(|smile| smile.iter().map(|(k, p)| (k.0, p)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Mark iv at `strike`, linearly interpolated between the neighbouring strikes.
+    /// `None` if `strike` is outside the quoted strikes.
+    pub fn mark_iv(&self, expiry: Expiry, strike: f64, kind: OptionKind) -> Option<f64> {
+        let smile = self.points.get(&(expiry, kind))?;
+        let (lk, lp) = smile.range(..=Strike(strike)).next_back()?;
+        if lk.0 == strike {
+            return Some(lp.mark_iv);
+        }
+        let (uk, up) = smile.range(Strike(strike)..).next()?;
+        let w = (strike - lk.0) / (uk.0 - lk.0);
+        Some(lp.mark_iv + w * (up.mark_iv - lp.mark_iv))
+    }
+}
+
+/// Bybit returns "0" iv for an empty side of the book.
+fn positive(v: &impl ToF64) -> Option<f64> {
+    v.to_f64().filter(|v| *v > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instrument() {
+        let i: OptionInstrument = "BTC-29DEC23-40000-C".parse().unwrap();
+        assert_eq!(i.base, "BTC");
+        assert_eq!(
+            i.expiry,
+            Expiry {
+                year: 2023,
+                month: 12,
+                day: 29
+            }
+        );
+        assert_eq!(i.strike, 40000.0);
+        assert_eq!(i.kind, OptionKind::Call);
+        assert_eq!(i.settle, None);
+        assert_eq!(i.to_string(), "BTC-29DEC23-40000-C");
+
+        let i: OptionInstrument = "ETH-1MAR24-3250.5-P-USDT".parse().unwrap();
+        assert_eq!(i.expiry.day, 1);
+        assert_eq!(i.strike, 3250.5);
+        assert_eq!(i.kind, OptionKind::Put);
+        assert_eq!(i.to_string(), "ETH-1MAR24-3250.5-P-USDT");
+
+        assert!("BTCUSDT".parse::<OptionInstrument>().is_err());
+        assert!("BTC-29XYZ23-40000-C".parse::<OptionInstrument>().is_err());
+        assert!("BTC-29DEC23-40000-X".parse::<OptionInstrument>().is_err());
+        assert!("1AAé1".parse::<Expiry>().is_err());
+        assert!("BTC-1AAé1-1-C".parse::<OptionInstrument>().is_err());
+
+        let expiry = Expiry {
+            year: 2023,
+            month: 13,
+            day: 1,
+        };
+        assert_eq!(expiry.to_string(), "1M1323");
+        assert_eq!(Expiry { month: 0, ..expiry }.to_string(), "1M023");
+    }
+
+    #[test]
+    fn test_filter_instruments() {
+        let expiry: Expiry = "29DEC23".parse().unwrap();
+        let symbols = [
+            "BTC-29DEC23-30000-C",
+            "BTC-29DEC23-40000-P",
+            "BTC-29DEC23-50000-C",
+            "BTC-26JAN24-40000-C",
+            "BTCUSDT",
+        ];
+        let instruments = filter_instruments(symbols, |i| {
+            i.expiry == expiry && (35000.0..=50000.0).contains(&i.strike)
+        });
+        assert_eq!(instruments.len(), 2);
+        assert!(expiry < "26JAN24".parse().unwrap());
+    }
+
+    fn update(
+        surface: &mut VolatilitySurface,
+        symbol: &str,
+        mark_iv: &str,
+    ) -> Option<OptionInstrument> {
+        let content = format!(
+            r#"{{"symbol":"{symbol}","bidPrice":"0","bidSize":"0","bidIv":"0","askPrice":"10","askSize":"1","askIv":"0.7","lastPrice":"10","highPrice24h":"10","lowPrice24h":"10","markPrice":"10","indexPrice":"40000","markPriceIv":"{mark_iv}","underlyingPrice":"40100","openInterest":"0","turnover24h":"0","volume24h":"0","totalVolume":"0","totalTurnover":"0","delta":"0.5","gamma":"0.0001","vega":"10","theta":"-20","predictedDeliveryPrice":"0","change24h":"0"}}"#
+        );
+        let ticker: OptionTicker = serde_json::from_str(&content).unwrap();
+        surface.update(&ticker, crate::timestamp::from_millis(1).unwrap())
+    }
+
+    #[test]
+    fn test_surface() {
+        let mut surface = VolatilitySurface::new("BTC");
+        update(&mut surface, "BTC-29DEC23-40000-C", "0.5");
+        update(&mut surface, "BTC-29DEC23-50000-C", "0.6");
+        update(&mut surface, "BTC-26JAN24-40000-C", "0.55");
+        assert!(update(&mut surface, "BTCUSDT", "0.5").is_none());
+        // Another base coin at the same expiry and strike.
+        assert!(update(&mut surface, "ETH-29DEC23-40000-C", "0.9").is_none());
+
+        let expiry: Expiry = "29DEC23".parse().unwrap();
+        let point = surface.get(expiry, 40000.0, OptionKind::Call).unwrap();
+        assert_eq!(point.bid_iv, None);
+        assert_eq!(point.ask_iv, Some(0.7));
+        assert_eq!(surface.expiries().len(), 2);
+        assert_eq!(surface.smile(expiry, OptionKind::Call).len(), 2);
+
+        let iv = surface.mark_iv(expiry, 45000.0, OptionKind::Call).unwrap();
+        assert!((iv - 0.55).abs() < 1e-9);
+        assert_eq!(
+            surface.mark_iv(expiry, 40000.0, OptionKind::Call),
+            Some(0.5)
+        );
+        assert_eq!(surface.mark_iv(expiry, 60000.0, OptionKind::Call), None);
+        assert_eq!(surface.mark_iv(expiry, 45000.0, OptionKind::Put), None);
+
+        surface.remove_expiry(expiry);
+        assert_eq!(surface.expiries().len(), 1);
+    }
+}
@@ -0,0 +1,897 @@
+//! Order lifecycles tracked from the private `order` and `execution` topics.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use tokio::sync::{broadcast, watch};
+
+use crate::enums::{Category, OrderStatus, OrderType, Side, StopOrderType};
+use crate::error::Result;
+use crate::num::ToF64;
+use crate::rest::order::{ExecutionInfo, OrderInfo};
+use crate::timestamp::{Timestamp, ToMillis};
+use crate::ws::response::{Execution, Order, PrivateResponse};
+use crate::RestApiClient;
+
+/// The IDs of an order accepted by a create, amend or cancel request, whether sent
+/// over the trade websocket or REST.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResult {
+    /// Order ID.
+    pub order_id: String,
+    /// User customised order ID.
+    pub order_link_id: String,
+}
+
+/// The state of an order tracked by [`OrderTracker`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedOrder {
+    pub category: Category,
+    pub symbol: String,
+    pub order_id: String,
+    /// User customised order ID.
+    pub order_link_id: String,
+    pub side: Side,
+    /// `None` if only executions of the order were received.
+    pub order_type: Option<OrderType>,
+    pub stop_order_type: Option<StopOrderType>,
+    pub status: OrderStatus,
+    /// Order price. Zero for market orders.
+    pub price: f64,
+    /// Order qty.
+    pub qty: f64,
+    /// Cumulative executed qty.
+    pub filled_qty: f64,
+    /// Average fill price. `None` if unfilled.
+    pub avg_price: Option<f64>,
+    /// Cumulative trading fee.
+    pub cum_fee: f64,
+    /// Reject reason.
+    pub reject_reason: String,
+    /// The timestamp of the last update.
+    pub updated_time: Timestamp,
+    /// The fill reported by order updates.
+    reported: Filled,
+    /// The fill summed from the executions applied.
+    executed: Filled,
+    exec_ids: HashSet<String>,
+}
+
+/// The cumulative fill of an order.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Filled {
+    qty: f64,
+    value: f64,
+    fee: f64,
+    avg_price: Option<f64>,
+}
+
+/// The fields of an order update from the private topic or REST.
+struct OrderUpdate<'a> {
+    category: Category,
+    symbol: &'a str,
+    order_id: &'a str,
+    order_link_id: &'a str,
+    side: &'a Side,
+    status: &'a OrderStatus,
+    order_type: &'a OrderType,
+    stop_order_type: &'a Option<StopOrderType>,
+    price: f64,
+    qty: f64,
+    reject_reason: &'a str,
+    updated_time: Timestamp,
+    cum_exec_qty: f64,
+    cum_exec_value: f64,
+    avg_price: Option<f64>,
+    cum_exec_fee: f64,
+}
+
+impl<'a> From<&'a Order<'_>> for OrderUpdate<'a> {
+    fn from(order: &'a Order) -> Self {
+        Self {
+            category: order.category.clone(),
+            symbol: order.symbol,
+            order_id: order.order_id,
+            order_link_id: order.order_link_id,
+            side: &order.side,
+            status: &order.order_status,
+            order_type: &order.order_type,
+            stop_order_type: &order.stop_order_type,
+            price: order.price.to_f64().unwrap_or_default(),
+            qty: order.qty.to_f64().unwrap_or_default(),
+            reject_reason: order.reject_reason,
+            updated_time: order.updated_time,
+            cum_exec_qty: order.cum_exec_qty.to_f64().unwrap_or_default(),
+            cum_exec_value: order.cum_exec_value.to_f64().unwrap_or_default(),
+            avg_price: order.avg_price.to_f64(),
+            cum_exec_fee: order.cum_exec_fee.to_f64().unwrap_or_default(),
+        }
+    }
+}
+
+impl<'a> OrderUpdate<'a> {
+    fn from_rest(category: Category, order: &'a OrderInfo) -> Self {
+        Self {
+            category,
+            symbol: &order.symbol,
+            order_id: &order.order_id,
+            order_link_id: &order.order_link_id,
+            side: &order.side,
+            status: &order.order_status,
+            order_type: &order.order_type,
+            stop_order_type: &order.stop_order_type,
+            price: order.price.to_f64().unwrap_or_default(),
+            qty: order.qty.to_f64().unwrap_or_default(),
+            reject_reason: &order.reject_reason,
+            updated_time: order.updated_time,
+            cum_exec_qty: order.cum_exec_qty.to_f64().unwrap_or_default(),
+            cum_exec_value: order.cum_exec_value.to_f64().unwrap_or_default(),
+            avg_price: order.avg_price.to_f64(),
+            cum_exec_fee: order.cum_exec_fee.to_f64().unwrap_or_default(),
+        }
+    }
+}
+
+/// The fields of an execution from the private topic or REST.
+struct Fill<'a> {
+    category: Category,
+    symbol: &'a str,
+    order_id: &'a str,
+    order_link_id: &'a str,
+    side: &'a Side,
+    order_type: &'a OrderType,
+    stop_order_type: &'a Option<StopOrderType>,
+    order_price: f64,
+    order_qty: f64,
+    leaves_qty: f64,
+    exec_id: &'a str,
+    exec_price: Option<f64>,
+    exec_qty: Option<f64>,
+    exec_fee: f64,
+    exec_time: Timestamp,
+}
+
+impl<'a> From<&'a Execution<'_>> for Fill<'a> {
+    fn from(exec: &'a Execution) -> Self {
+        Self {
+            category: exec.category.clone(),
+            symbol: exec.symbol,
+            order_id: exec.order_id,
+            order_link_id: exec.order_link_id,
+            side: &exec.side,
+            order_type: &exec.order_type,
+            stop_order_type: &exec.stop_order_type,
+            order_price: exec.order_price.to_f64().unwrap_or_default(),
+            order_qty: exec.order_qty.to_f64().unwrap_or_default(),
+            leaves_qty: exec.leaves_qty.to_f64().unwrap_or_default(),
+            exec_id: exec.exec_id,
+            exec_price: exec.exec_price.to_f64(),
+            exec_qty: exec.exec_qty.to_f64(),
+            exec_fee: exec.exec_fee.to_f64().unwrap_or_default(),
+            exec_time: exec.exec_time,
+        }
+    }
+}
+
+impl<'a> Fill<'a> {
+    fn from_rest(category: Category, exec: &'a ExecutionInfo) -> Self {
+        Self {
+            category,
+            symbol: &exec.symbol,
+            order_id: &exec.order_id,
+            order_link_id: &exec.order_link_id,
+            side: &exec.side,
+            order_type: &exec.order_type,
+            stop_order_type: &exec.stop_order_type,
+            order_price: exec.order_price.to_f64().unwrap_or_default(),
+            order_qty: exec.order_qty.to_f64().unwrap_or_default(),
+            leaves_qty: exec.leaves_qty.to_f64().unwrap_or_default(),
+            exec_id: &exec.exec_id,
+            exec_price: exec.exec_price.to_f64(),
+            exec_qty: exec.exec_qty.to_f64(),
+            exec_fee: exec.exec_fee.to_f64().unwrap_or_default(),
+            exec_time: exec.exec_time,
+        }
+    }
+}
+
+impl TrackedOrder {
+    fn from_order(order: &OrderUpdate) -> Self {
+        let mut tracked = Self {
+            category: order.category.clone(),
+            symbol: order.symbol.to_owned(),
+            order_id: order.order_id.to_owned(),
+            order_link_id: order.order_link_id.to_owned(),
+            side: order.side.clone(),
+            order_type: None,
+            stop_order_type: None,
+            // The lowest rank, so that `update` applies any status.
+            status: OrderStatus::Untriggered,
+            price: 0.0,
+            qty: 0.0,
+            filled_qty: 0.0,
+            avg_price: None,
+            cum_fee: 0.0,
+            reject_reason: String::new(),
+            updated_time: order.updated_time,
+            reported: Filled::default(),
+            executed: Filled::default(),
+            exec_ids: HashSet::new(),
+        };
+        tracked.update(order);
+        tracked
+    }
+
+    fn from_execution(exec: &Fill) -> Self {
+        Self {
+            category: exec.category.clone(),
+            symbol: exec.symbol.to_owned(),
+            order_id: exec.order_id.to_owned(),
+            order_link_id: exec.order_link_id.to_owned(),
+            side: exec.side.clone(),
+            order_type: Some(exec.order_type.clone()),
+            stop_order_type: exec.stop_order_type.clone(),
+            status: OrderStatus::New,
+            price: exec.order_price,
+            qty: exec.order_qty,
+            filled_qty: 0.0,
+            avg_price: None,
+            cum_fee: 0.0,
+            reject_reason: String::new(),
+            updated_time: exec.exec_time,
+            reported: Filled::default(),
+            executed: Filled::default(),
+            exec_ids: HashSet::new(),
+        }
+    }
+
+    /// Whether the order reached a final status.
+    pub fn is_terminal(&self) -> bool {
+        self.status.is_terminal()
+    }
+
+    fn update(&mut self, order: &OrderUpdate) {
+        if self.is_terminal() || rank(order.status) < rank(&self.status) {
+            return;
+        }
+        self.status = order.status.clone();
+        self.order_type = Some(order.order_type.clone());
+        self.stop_order_type = order.stop_order_type.clone();
+        self.price = order.price;
+        self.qty = order.qty;
+        self.reject_reason = order.reject_reason.to_owned();
+        if order.updated_time.to_millis() > self.updated_time.to_millis() {
+            self.updated_time = order.updated_time;
+        }
+
+        if order.cum_exec_qty >= self.reported.qty {
+            self.reported = Filled {
+                qty: order.cum_exec_qty,
+                value: order.cum_exec_value,
+                fee: order.cum_exec_fee,
+                avg_price: order.avg_price.filter(|p| *p > 0.0),
+            };
+            self.refresh_fill();
+        }
+    }
+
+    /// Return `false` if the execution was applied before.
+    fn execute(&mut self, exec: &Fill) -> bool {
+        if !self.exec_ids.insert(exec.exec_id.to_owned()) {
+            return false;
+        }
+        let (Some(price), Some(qty)) = (exec.exec_price, exec.exec_qty) else {
+            return true;
+        };
+        let executed = &mut self.executed;
+        executed.qty += qty;
+        executed.value += price * qty;
+        executed.fee += exec.exec_fee;
+        if executed.qty > 0.0 {
+            executed.avg_price = Some(executed.value / executed.qty);
+        }
+        self.refresh_fill();
+        if exec.exec_time.to_millis() > self.updated_time.to_millis() {
+            self.updated_time = exec.exec_time;
+        }
+        if !self.is_terminal() {
+            self.status = if exec.leaves_qty > 0.0 {
+                OrderStatus::PartiallyFilled
+            } else {
+                OrderStatus::Filled
+            };
+        }
+        true
+    }
+
+    /// Expose the fill of whichever source is further ahead. Order updates and executions
+    /// arrive in any order, and either may be missed, e.g. before subscribing.
+    fn refresh_fill(&mut self) {
+        let fill = if self.executed.qty >= self.reported.qty {
+            &self.executed
+        } else {
+            &self.reported
+        };
+        self.filled_qty = fill.qty;
+        self.avg_price = fill.avg_price;
+        self.cum_fee = fill.fee;
+    }
+}
+
+/// The order of statuses in a lifecycle. An update to an earlier status is out of order.
+fn rank(status: &OrderStatus) -> u8 {
+    match status {
+        OrderStatus::Untriggered => 0,
+        OrderStatus::Triggered => 1,
+        OrderStatus::PartiallyFilled => 3,
+        s if s.is_terminal() => 4,
+        _ => 2,
+    }
+}
+
+/// The execution list covers at most 7 days per request.
+const EXECUTION_WINDOW: u64 = 7 * 24 * 60 * 60 * 1000;
+
+#[derive(Default)]
+struct Orders {
+    orders: HashMap<String, TrackedOrder>,
+    link_ids: HashMap<String, String>,
+    /// Kept by `prune`, so that executions fetched again by `reconcile` are not taken for
+    /// missed ones once their orders are gone.
+    last_execs: HashMap<Category, LastExec>,
+    /// The number of `wait_terminal` calls of each `order_link_id`. Their orders are kept
+    /// by `prune` until the waiters have read them.
+    waiting: HashMap<String, usize>,
+}
+
+/// Registers a `wait_terminal` call in `Orders::waiting` until it returns or is dropped.
+struct Waiting<'a> {
+    orders: &'a Mutex<Orders>,
+    order_link_id: &'a str,
+}
+
+impl<'a> Waiting<'a> {
+    fn new(orders: &'a Mutex<Orders>, order_link_id: &'a str) -> Self {
+        let mut guard = orders.lock().unwrap();
+        *guard.waiting.entry(order_link_id.to_owned()).or_default() += 1;
+        Self {
+            orders,
+            order_link_id,
+        }
+    }
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        let mut orders = self.orders.lock().unwrap();
+        if let Some(count) = orders.waiting.get_mut(self.order_link_id) {
+            *count -= 1;
+            if *count == 0 {
+                orders.waiting.remove(self.order_link_id);
+            }
+        }
+    }
+}
+
+/// The executions at the latest `exec_time` of a category.
+struct LastExec {
+    time: Timestamp,
+    exec_ids: HashSet<String>,
+}
+
+impl Orders {
+    fn insert(&mut self, order: TrackedOrder) -> &mut TrackedOrder {
+        if !order.order_link_id.is_empty() {
+            self.link_ids
+                .insert(order.order_link_id.clone(), order.order_id.clone());
+        }
+        self.orders.entry(order.order_id.clone()).or_insert(order)
+    }
+
+    fn by_link_id(&self, order_link_id: &str) -> Option<&TrackedOrder> {
+        self.orders.get(self.link_ids.get(order_link_id)?)
+    }
+
+    fn update(&mut self, order: &OrderUpdate) {
+        self.insert(TrackedOrder::from_order(order)).update(order);
+    }
+
+    /// Return `false` if the execution was ignored or applied before.
+    fn execute(&mut self, exec: &Fill) -> bool {
+        if exec.order_id.is_empty() {
+            return false;
+        }
+        let time = exec.exec_time.to_millis();
+        match self.last_execs.get_mut(&exec.category) {
+            Some(last) if time < last.time.to_millis() => {}
+            Some(last) if time == last.time.to_millis() => {
+                last.exec_ids.insert(exec.exec_id.to_owned());
+            }
+            _ => {
+                let last = LastExec {
+                    time: exec.exec_time,
+                    exec_ids: HashSet::from([exec.exec_id.to_owned()]),
+                };
+                self.last_execs.insert(exec.category.clone(), last);
+            }
+        }
+        self.insert(TrackedOrder::from_execution(exec))
+            .execute(exec)
+    }
+
+    /// Whether an execution is at or before the latest one applied of its category.
+    /// Executions at the same `exec_time` are told apart by `exec_id`.
+    fn is_seen(&self, exec: &Fill) -> bool {
+        let Some(last) = self.last_execs.get(&exec.category) else {
+            return false;
+        };
+        let time = exec.exec_time.to_millis();
+        time < last.time.to_millis()
+            || (time == last.time.to_millis() && last.exec_ids.contains(exec.exec_id))
+    }
+
+    /// Apply an order from REST and report how it differs from the tracked one.
+    fn reconcile(&mut self, order: &OrderUpdate) -> Option<Discrepancy> {
+        let status = self.orders.get(order.order_id).map(|o| o.status.clone());
+        self.update(order);
+        match status {
+            None => Some(Discrepancy::Untracked {
+                order_id: order.order_id.to_owned(),
+                order_link_id: order.order_link_id.to_owned(),
+                status: order.status.clone(),
+            }),
+            Some(from) => {
+                let to = &self.orders[order.order_id].status;
+                (from != *to).then(|| Discrepancy::Status {
+                    order_id: order.order_id.to_owned(),
+                    from,
+                    to: to.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// A change of [`OrderTracker`], received from the private topics or synthesized by
+/// [`OrderTracker::reconcile`] for what was missed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEvent {
+    /// An order was updated.
+    Order { order_id: String },
+    /// An execution was applied to an order.
+    Execution { order_id: String, exec_id: String },
+}
+
+/// A difference between [`OrderTracker`] and the exchange found by
+/// [`OrderTracker::reconcile`]. The tracker is updated to match the exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Discrepancy {
+    /// An order unknown to the tracker, e.g. placed while disconnected.
+    Untracked {
+        order_id: String,
+        order_link_id: String,
+        status: OrderStatus,
+    },
+    /// The status of a tracked order changed while disconnected. A change caused by a
+    /// missed execution is reported as [`Discrepancy::Execution`] only.
+    Status {
+        order_id: String,
+        from: OrderStatus,
+        to: OrderStatus,
+    },
+    /// An execution missed while disconnected.
+    Execution { order_id: String, exec_id: String },
+    /// An open order of the tracker which the exchange does not know.
+    Missing { order_id: String },
+}
+
+/// Tracks the lifecycle of orders from the private `order` and `execution` topics.
+///
+/// Executions are de-duplicated by `exec_id`, and out of order status updates, e.g. `New`
+/// after `PartiallyFilled`, are ignored. Clones share the same orders, so a clone fed by
+/// the private channel callback wakes up the ones waiting in [`OrderTracker::wait_terminal`].
+#[derive(Clone)]
+pub struct OrderTracker {
+    orders: Arc<Mutex<Orders>>,
+    updated: Arc<watch::Sender<()>>,
+    events: broadcast::Sender<OrderEvent>,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self {
+            orders: Arc::default(),
+            updated: Arc::new(watch::channel(()).0),
+            events: broadcast::channel(256).0,
+        }
+    }
+
+    /// Route the `order` and `execution` topics of the private channel to
+    /// [`OrderTracker::on_order`] and [`OrderTracker::on_execution`].
+    pub fn apply(&self, res: &PrivateResponse) {
+        match res {
+            PrivateResponse::Order(res) => res.data.iter().for_each(|o| self.on_order(o)),
+            PrivateResponse::Execution(res) => res.data.iter().for_each(|e| self.on_execution(e)),
+            _ => {}
+        }
+    }
+
+    /// Apply an order update.
+    pub fn on_order(&self, order: &Order) {
+        self.orders.lock().unwrap().update(&order.into());
+        self.updated.send_replace(());
+        let _ = self.events.send(OrderEvent::Order {
+            order_id: order.order_id.to_owned(),
+        });
+    }
+
+    /// Apply an execution. Executions without an order, e.g. funding fees, are ignored.
+    pub fn on_execution(&self, exec: &Execution) {
+        let executed = self.orders.lock().unwrap().execute(&exec.into());
+        if executed {
+            self.updated.send_replace(());
+            let _ = self.events.send(OrderEvent::Execution {
+                order_id: exec.order_id.to_owned(),
+                exec_id: exec.exec_id.to_owned(),
+            });
+        }
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<TrackedOrder> {
+        self.orders.lock().unwrap().orders.get(order_id).cloned()
+    }
+
+    pub fn get_by_link_id(&self, order_link_id: &str) -> Option<TrackedOrder> {
+        self.orders
+            .lock()
+            .unwrap()
+            .by_link_id(order_link_id)
+            .cloned()
+    }
+
+    /// Orders which have not reached a final status.
+    pub fn open_orders(&self) -> Vec<TrackedOrder> {
+        let orders = self.orders.lock().unwrap();
+        orders
+            .orders
+            .values()
+            .filter(|o| !o.is_terminal())
+            .cloned()
+            .collect()
+    }
+
+    /// The latest `exec_time` of the executions of `category` applied.
+    pub fn last_exec_time(&self, category: &Category) -> Option<Timestamp> {
+        let orders = self.orders.lock().unwrap();
+        orders.last_execs.get(category).map(|last| last.time)
+    }
+
+    /// Receive an event after each change, including the ones synthesized by
+    /// [`OrderTracker::reconcile`]. A lagging receiver misses the oldest events.
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderEvent> {
+        self.events.subscribe()
+    }
+
+    /// Remove the orders which reached a final status, except the ones still awaited by
+    /// [`OrderTracker::wait_terminal`].
+    pub fn prune(&self) {
+        let mut orders = self.orders.lock().unwrap();
+        let Orders {
+            orders,
+            link_ids,
+            waiting,
+            ..
+        } = &mut *orders;
+        orders.retain(|_, o| !o.is_terminal() || waiting.contains_key(&o.order_link_id));
+        link_ids.retain(|_, id| orders.contains_key(id));
+    }
+
+    /// Wait until the order of `order_link_id` reaches a final status.
+    /// Wrap it with `tokio::time::timeout` to give up waiting.
+    pub async fn wait_terminal(&self, order_link_id: &str) -> TrackedOrder {
+        let _waiting = Waiting::new(&self.orders, order_link_id);
+        let mut rx = self.updated.subscribe();
+        loop {
+            if let Some(order) = self
+                .get_by_link_id(order_link_id)
+                .filter(TrackedOrder::is_terminal)
+            {
+                return order;
+            }
+            // The sender lives as long as `self`.
+            let _ = rx.changed().await;
+        }
+    }
+
+    /// Bring the orders of `category` up to date with the exchange, e.g. after the private
+    /// channel reconnects, and return the differences found.
+    ///
+    /// The executions of `category` since its last seen `exec_time` are fetched and applied
+    /// first, then the open orders. Tracked open orders which are no longer open are looked
+    /// up in the order history. `settle_coin` is required for `linear` and `inverse`, e.g.
+    /// `USDT`. An [`OrderEvent`] is sent for every missed order update and execution, as if
+    /// it had been received from the private topics.
+    pub async fn reconcile(
+        &self,
+        client: &RestApiClient,
+        category: Category,
+        settle_coin: Option<&str>,
+    ) -> Result<Vec<Discrepancy>> {
+        let start_time = self.last_exec_time(&category).or_else(|| {
+            self.open_orders()
+                .iter()
+                .filter(|o| o.category == category)
+                .map(|o| o.updated_time)
+                .min_by_key(ToMillis::to_millis)
+        });
+        let mut executions = Vec::new();
+        if let Some(start_time) = start_time {
+            let now = client.clock().timestamp() as u64;
+            let mut start = start_time.to_millis();
+            while start <= now {
+                let end = start + EXECUTION_WINDOW - 1;
+                executions.extend(client.executions(category.clone(), start, end).await?);
+                start = end + 1;
+            }
+        }
+        // Pages are in descending order of `exec_time`.
+        executions.sort_by_key(|e| e.exec_time.to_millis());
+        let open_orders = client.open_orders(category.clone(), settle_coin).await?;
+
+        let mut discrepancies = self.reconcile_with(&category, &executions, &open_orders);
+        let closed: Vec<String> = self
+            .open_orders()
+            .into_iter()
+            .filter(|o| o.category == category)
+            .filter(|o| !open_orders.iter().any(|open| open.order_id == o.order_id))
+            .map(|o| o.order_id)
+            .collect();
+        for order_id in closed {
+            let discrepancy = match client.order_history(category.clone(), &order_id).await? {
+                Some(order) => self
+                    .orders
+                    .lock()
+                    .unwrap()
+                    .reconcile(&OrderUpdate::from_rest(category.clone(), &order)),
+                None => Some(Discrepancy::Missing { order_id }),
+            };
+            discrepancies.extend(discrepancy);
+        }
+        self.synthesize(&discrepancies);
+        Ok(discrepancies)
+    }
+
+    /// Notify the waiters and subscribers of the changes found by `reconcile`.
+    fn synthesize(&self, discrepancies: &[Discrepancy]) {
+        if !discrepancies.is_empty() {
+            self.updated.send_replace(());
+        }
+        for discrepancy in discrepancies {
+            let event = match discrepancy {
+                Discrepancy::Untracked { order_id, .. } | Discrepancy::Status { order_id, .. } => {
+                    OrderEvent::Order {
+                        order_id: order_id.clone(),
+                    }
+                }
+                Discrepancy::Execution { order_id, exec_id } => OrderEvent::Execution {
+                    order_id: order_id.clone(),
+                    exec_id: exec_id.clone(),
+                },
+                Discrepancy::Missing { .. } => continue,
+            };
+            let _ = self.events.send(event);
+        }
+    }
+
+    fn reconcile_with(
+        &self,
+        category: &Category,
+        executions: &[ExecutionInfo],
+        open_orders: &[OrderInfo],
+    ) -> Vec<Discrepancy> {
+        let mut orders = self.orders.lock().unwrap();
+        let mut discrepancies = Vec::new();
+        for exec in executions {
+            let fill = Fill::from_rest(category.clone(), exec);
+            if !orders.is_seen(&fill) && orders.execute(&fill) {
+                discrepancies.push(Discrepancy::Execution {
+                    order_id: exec.order_id.clone(),
+                    exec_id: exec.exec_id.clone(),
+                });
+            }
+        }
+        for order in open_orders {
+            discrepancies
+                .extend(orders.reconcile(&OrderUpdate::from_rest(category.clone(), order)));
+        }
+        discrepancies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: &str = r#"{"id":"1","topic":"order","creationTime":1672364262474,"data":[{"symbol":"XRPUSDT","orderId":"f6e324ff","side":"Sell","orderType":"Limit","cancelType":"UNKNOWN","price":"0.3374","qty":"50","orderIv":"","timeInForce":"GTC","orderStatus":"New","orderLinkId":"link-1","lastPriceOnCreated":"","reduceOnly":false,"leavesQty":"","leavesValue":"","cumExecQty":"0","cumExecValue":"0","avgPrice":"","blockTradeId":"","positionIdx":0,"cumExecFee":"0","createdTime":"1672364262444","updatedTime":"1672364262450","rejectReason":"EC_NoError","stopOrderType":"","tpslMode":"","triggerPrice":"","takeProfit":"","stopLoss":"","tpTriggerBy":"","slTriggerBy":"","tpLimitPrice":"","slLimitPrice":"","triggerDirection":0,"triggerBy":"","closeOnTrigger":false,"category":"linear","placeType":"","smpType":"None","smpGroup":0,"smpOrderId":"","isLeverage":""}]}"#;
+
+    const EXECUTION: &str = r#"{"id":"2","topic":"execution","creationTime":1672364174455,"data":[{"category":"linear","symbol":"XRPUSDT","execFee":"0.005","execId":"e1","execPrice":"0.3374","execQty":"25","execType":"Trade","execValue":"8.435","isMaker":true,"feeRate":"0.0002","tradeIv":"","markIv":"","blockTradeId":"","markPrice":"0.3391","indexPrice":"","underlyingPrice":"","leavesQty":"25","orderId":"f6e324ff","orderLinkId":"link-1","orderPrice":"0.3374","orderQty":"50","orderType":"Limit","stopOrderType":"","side":"Sell","execTime":"1672364174443","isLeverage":"0","closedSize":"","seq":4688002127}]}"#;
+
+    #[tokio::test]
+    async fn test_order_tracker() {
+        let tracker = OrderTracker::new();
+        let waiter = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.wait_terminal("link-1").await }
+        });
+
+        tracker.apply(&serde_json::from_str(EXECUTION).unwrap());
+        tracker.apply(&serde_json::from_str(EXECUTION).unwrap());
+        tracker.apply(&serde_json::from_str(ORDER).unwrap());
+        let tracked = tracker.get_by_link_id("link-1").unwrap();
+        assert_eq!(tracked.status, OrderStatus::PartiallyFilled);
+        assert_eq!(tracked.filled_qty, 25.0);
+        assert_eq!(tracker.open_orders().len(), 1);
+
+        let e2 = EXECUTION
+            .replace(r#""execId":"e1""#, r#""execId":"e2""#)
+            .replace(r#""execPrice":"0.3374""#, r#""execPrice":"0.3376""#)
+            .replace(r#""leavesQty":"25""#, r#""leavesQty":"0""#);
+        tracker.apply(&serde_json::from_str(&e2).unwrap());
+        let filled = ORDER
+            .replace(r#""orderStatus":"New""#, r#""orderStatus":"Filled""#)
+            .replace(r#""cumExecQty":"0""#, r#""cumExecQty":"50""#)
+            .replace("1672364262450", "1672364262460");
+        tracker.apply(&serde_json::from_str(&filled).unwrap());
+        // Out of order.
+        let partially_filled = ORDER
+            .replace(
+                r#""orderStatus":"New""#,
+                r#""orderStatus":"PartiallyFilled""#,
+            )
+            .replace(r#""cumExecQty":"0""#, r#""cumExecQty":"25""#)
+            .replace("1672364262450", "1672364262455");
+        tracker.apply(&serde_json::from_str(&partially_filled).unwrap());
+
+        let tracked = tokio::time::timeout(std::time::Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tracked.status, OrderStatus::Filled);
+        assert_eq!(tracked.filled_qty, 50.0);
+        assert!((tracked.avg_price.unwrap() - 0.3375).abs() < 1e-9);
+        assert!((tracked.cum_fee - 0.01).abs() < 1e-9);
+        assert_eq!(tracked.order_type, Some(OrderType::Limit));
+
+        // The first update of an order can be final.
+        tracker.apply(&serde_json::from_str(&filled.replace("f6e324ff", "a1")).unwrap());
+        let tracked = tracker.get("a1").unwrap();
+        assert_eq!((tracked.qty, tracked.filled_qty), (50.0, 50.0));
+
+        // The order update arrives before its execution.
+        let partially_filled = partially_filled
+            .replace("f6e324ff", "b2")
+            .replace(r#""cumExecValue":"0""#, r#""cumExecValue":"8.435""#)
+            .replace(r#""avgPrice":"""#, r#""avgPrice":"0.3374""#)
+            .replace(r#""cumExecFee":"0""#, r#""cumExecFee":"0.005""#);
+        tracker.apply(&serde_json::from_str(&partially_filled).unwrap());
+        tracker.apply(&serde_json::from_str(&EXECUTION.replace("f6e324ff", "b2")).unwrap());
+        let tracked = tracker.get("b2").unwrap();
+        assert_eq!(tracked.filled_qty, 25.0);
+        assert!((tracked.cum_fee - 0.005).abs() < 1e-9);
+        assert!((tracked.avg_price.unwrap() - 0.3374).abs() < 1e-9);
+
+        tracker.prune();
+        assert!(tracker.get("f6e324ff").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_while_waiting() {
+        let tracker = OrderTracker::new();
+        tracker.apply(&serde_json::from_str(ORDER).unwrap());
+        let waiter = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.wait_terminal("link-1").await }
+        });
+        tokio::task::yield_now().await;
+
+        let filled = ORDER
+            .replace(r#""orderStatus":"New""#, r#""orderStatus":"Filled""#)
+            .replace("1672364262450", "1672364262460");
+        tracker.apply(&serde_json::from_str(&filled).unwrap());
+        // The order is kept until the waiter reads it.
+        tracker.prune();
+        assert!(tracker.get("f6e324ff").is_some());
+
+        let tracked = tokio::time::timeout(std::time::Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tracked.status, OrderStatus::Filled);
+        tracker.prune();
+        assert!(tracker.get("f6e324ff").is_none());
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let tracker = OrderTracker::new();
+        let mut events = tracker.subscribe();
+        tracker.apply(&serde_json::from_str(ORDER).unwrap());
+        assert!(events.try_recv().is_ok());
+        assert_eq!(tracker.last_exec_time(&Category::Linear), None);
+
+        let executions: Vec<ExecutionInfo> = serde_json::from_str(r#"[{"symbol":"XRPUSDT","orderId":"f6e324ff","orderLinkId":"link-1","side":"Sell","orderPrice":"0.3374","orderQty":"50","leavesQty":"25","orderType":"Limit","stopOrderType":"UNKNOWN","execFee":"0.005","execId":"e1","execPrice":"0.3374","execQty":"25","execType":"Trade","execValue":"8.435","feeRate":"0.0002","execTime":"1672364174443","isMaker":true}]"#).unwrap();
+        let open_orders: Vec<OrderInfo> = serde_json::from_str(r#"[{"orderId":"f6e324ff","orderLinkId":"link-1","symbol":"XRPUSDT","price":"0.3374","qty":"50","side":"Sell","positionIdx":0,"orderStatus":"PartiallyFilled","rejectReason":"EC_NoError","avgPrice":"0.3374","cumExecQty":"25","cumExecValue":"8.435","cumExecFee":"0.005","orderType":"Limit","stopOrderType":"","createdTime":"1672364262444","updatedTime":"1672364262460"},{"orderId":"b2","orderLinkId":"link-2","symbol":"XRPUSDT","price":"0.3374","qty":"50","side":"Sell","positionIdx":0,"orderStatus":"New","rejectReason":"EC_NoError","avgPrice":"","cumExecQty":"0","cumExecValue":"0","cumExecFee":"0","orderType":"Limit","stopOrderType":"","createdTime":"1672364262444","updatedTime":"1672364262460"}]"#).unwrap();
+
+        let discrepancies = tracker.reconcile_with(&Category::Linear, &executions, &open_orders);
+        assert_eq!(
+            discrepancies,
+            vec![
+                Discrepancy::Execution {
+                    order_id: "f6e324ff".to_owned(),
+                    exec_id: "e1".to_owned(),
+                },
+                Discrepancy::Untracked {
+                    order_id: "b2".to_owned(),
+                    order_link_id: "link-2".to_owned(),
+                    status: OrderStatus::New,
+                },
+            ]
+        );
+        let tracked = tracker.get("f6e324ff").unwrap();
+        assert_eq!(tracked.status, OrderStatus::PartiallyFilled);
+        assert_eq!(tracked.filled_qty, 25.0);
+        assert!((tracked.cum_fee - 0.005).abs() < 1e-9);
+        assert_eq!(tracker.open_orders().len(), 2);
+        assert_eq!(
+            tracker
+                .last_exec_time(&Category::Linear)
+                .unwrap()
+                .to_millis(),
+            1672364174443
+        );
+        assert_eq!(tracker.last_exec_time(&Category::Spot), None);
+
+        tracker.synthesize(&discrepancies);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            OrderEvent::Execution {
+                order_id: "f6e324ff".to_owned(),
+                exec_id: "e1".to_owned(),
+            }
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            OrderEvent::Order {
+                order_id: "b2".to_owned(),
+            }
+        );
+
+        // Reconciling again finds nothing new.
+        assert!(tracker
+            .reconcile_with(&Category::Linear, &executions, &open_orders)
+            .is_empty());
+
+        // The last execution is fetched again after its order is filled and pruned.
+        let executions: Vec<ExecutionInfo> = serde_json::from_str(r#"[{"symbol":"XRPUSDT","orderId":"f6e324ff","orderLinkId":"link-1","side":"Sell","orderPrice":"0.3374","orderQty":"50","leavesQty":"0","orderType":"Limit","stopOrderType":"UNKNOWN","execFee":"0.005","execId":"e2","execPrice":"0.3374","execQty":"25","execType":"Trade","execValue":"8.435","feeRate":"0.0002","execTime":"1672364175000","isMaker":true}]"#).unwrap();
+        assert_eq!(
+            tracker.reconcile_with(&Category::Linear, &executions, &open_orders[1..]),
+            vec![Discrepancy::Execution {
+                order_id: "f6e324ff".to_owned(),
+                exec_id: "e2".to_owned(),
+            }]
+        );
+        tracker.prune();
+        assert!(tracker
+            .reconcile_with(&Category::Linear, &executions, &open_orders[1..])
+            .is_empty());
+        assert!(tracker.get("f6e324ff").is_none());
+    }
+}
@@ -0,0 +1,673 @@
+//! A local orderbook maintained from the snapshots and deltas of the `orderbook` topic.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::watch;
+
+use crate::enums::{Category, Side};
+use crate::error::{BybitError, Result};
+use crate::num::{Num, NumOwned, ToF64};
+use crate::timestamp::Timestamp;
+use crate::ws::response::{BasePublicResponse, Orderbook, OrderbookItem};
+use crate::ws::Resubscriber;
+
+/// A price level of [`LocalOrderbook`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub price: NumOwned,
+    pub size: NumOwned,
+}
+
+#[cfg(not(feature = "rust_decimal"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PriceKey(f64);
+
+#[cfg(not(feature = "rust_decimal"))]
+impl Eq for PriceKey {}
+
+#[cfg(not(feature = "rust_decimal"))]
+impl PartialOrd for PriceKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(not(feature = "rust_decimal"))]
+impl Ord for PriceKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+type PriceKey = crate::num::Decimal;
+
+#[cfg(not(feature = "rust_decimal"))]
+fn price_key(price: f64) -> Option<PriceKey> {
+    Some(PriceKey(price))
+}
+
+#[cfg(feature = "rust_decimal")]
+fn price_key(price: f64) -> Option<PriceKey> {
+    PriceKey::try_from(price).ok()
+}
+
+#[cfg(not(feature = "rust_decimal"))]
+fn level_key(price: &Num) -> Option<PriceKey> {
+    price.to_f64().map(PriceKey)
+}
+
+#[cfg(feature = "rust_decimal")]
+fn level_key(price: &Num) -> Option<PriceKey> {
+    Some(*price)
+}
+
+/// A resync of [`LocalOrderbook`] reported to the event callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderbookEvent {
+    /// A gap was detected and `topic` was resubscribed. The book is stale until the next snapshot.
+    Resync { symbol: String, topic: String },
+    /// A fresh snapshot was applied after a resync.
+    Resynced { symbol: String, update_id: u64 },
+}
+
+/// An in-memory orderbook of a symbol.
+///
+/// Snapshots overwrite the book. Deltas must continue the update ID of the previous
+/// message, otherwise the book is out of sync and rejects deltas until the next snapshot.
+/// With a [`Resubscriber`], the topic is resubscribed on a gap to receive the next snapshot.
+pub struct LocalOrderbook {
+    symbol: String,
+    bids: BTreeMap<PriceKey, Level>,
+    asks: BTreeMap<PriceKey, Level>,
+    update_id: u64,
+    seq: Option<u64>,
+    ts: Option<Timestamp>,
+    synced: bool,
+    resyncing: bool,
+    resubscriber: Option<Resubscriber>,
+    on_event: Option<Box<dyn FnMut(OrderbookEvent) + Send + Sync>>,
+}
+
+impl LocalOrderbook {
+    pub fn new<S: Into<String>>(symbol: S) -> Self {
+        Self {
+            symbol: symbol.into(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            update_id: 0,
+            seq: None,
+            ts: None,
+            synced: false,
+            resyncing: false,
+            resubscriber: None,
+            on_event: None,
+        }
+    }
+
+    /// Resubscribe the topic with `resubscriber` when the book gets out of sync.
+    /// `resubscriber` should be taken from the client which receives the topic.
+    pub fn resubscriber(mut self, resubscriber: Resubscriber) -> Self {
+        self.resubscriber = Some(resubscriber);
+        self
+    }
+
+    /// Report resyncs to `on_event`.
+    pub fn on_event<F>(mut self, on_event: F) -> Self
+    where
+        F: FnMut(OrderbookEvent) + Send + Sync + 'static,
+    {
+        self.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    /// Apply a snapshot or delta.
+    ///
+    /// A gap in the update ID or a cross sequence which does not increase puts the book
+    /// out of sync, and the topic is resubscribed if a [`Resubscriber`] is set.
+    pub fn apply(&mut self, res: &BasePublicResponse<Orderbook>) -> Result<()> {
+        let applied = self.apply_inner(res);
+        if let Err(BybitError::OrderbookGap { .. } | BybitError::OrderbookSequence { .. }) = applied
+        {
+            self.resync(res.topic);
+        }
+        applied
+    }
+
+    /// Mark the book as stale and resubscribe `topic` to receive a fresh snapshot.
+    /// The topic is not resubscribed without a [`Resubscriber`] or if a resync is in progress.
+    pub fn resync(&mut self, topic: &str) {
+        self.synced = false;
+        let Some(resubscriber) = &self.resubscriber else {
+            return;
+        };
+        if self.resyncing {
+            return;
+        }
+        self.resyncing = true;
+        resubscriber.resubscribe(topic);
+        self.emit(OrderbookEvent::Resync {
+            symbol: self.symbol.clone(),
+            topic: topic.to_owned(),
+        });
+    }
+
+    fn apply_inner(&mut self, res: &BasePublicResponse<Orderbook>) -> Result<()> {
+        let data = &res.data;
+        if data.s != self.symbol {
+            return Err(BybitError::InvalidSymbol(data.s.to_string()));
+        }
+
+        // "u"=1 is a snapshot due to the restart of the service.
+        let snapshot = res.type_ == "snapshot" || data.u == 1;
+        if snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        } else {
+            if !self.synced {
+                return Err(BybitError::OrderbookNotSynced(self.symbol.clone()));
+            }
+            if data.u != self.update_id + 1 {
+                self.synced = false;
+                return Err(BybitError::OrderbookGap {
+                    symbol: self.symbol.clone(),
+                    expected: self.update_id + 1,
+                    received: data.u,
+                });
+            }
+            if let (Some(last), Some(seq)) = (self.seq, data.seq) {
+                if seq <= last {
+                    self.synced = false;
+                    return Err(BybitError::OrderbookSequence {
+                        symbol: self.symbol.clone(),
+                        last,
+                        received: seq,
+                    });
+                }
+            }
+        }
+
+        let applied = update(&mut self.bids, &data.b).and_then(|_| update(&mut self.asks, &data.a));
+        self.synced = applied.is_ok();
+        self.update_id = data.u;
+        self.seq = data.seq;
+        self.ts = Some(res.ts);
+        if snapshot && self.synced && self.resyncing {
+            self.resyncing = false;
+            self.emit(OrderbookEvent::Resynced {
+                symbol: self.symbol.clone(),
+                update_id: self.update_id,
+            });
+        }
+        applied
+    }
+
+    fn emit(&mut self, event: OrderbookEvent) {
+        if let Some(on_event) = self.on_event.as_mut() {
+            on_event(event);
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Whether the book reflects all received updates since the last snapshot.
+    /// A stale book waiting for a resync is not synced.
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// The update ID of the last applied message.
+    pub fn update_id(&self) -> u64 {
+        self.update_id
+    }
+
+    /// The cross sequence of the last applied message.
+    pub fn seq(&self) -> Option<u64> {
+        self.seq
+    }
+
+    /// The timestamp of the last applied message.
+    pub fn ts(&self) -> Option<Timestamp> {
+        self.ts
+    }
+
+    /// Bids in descending price order.
+    pub fn bids(&self) -> impl DoubleEndedIterator<Item = &Level> {
+        self.bids.values().rev()
+    }
+
+    /// Asks in ascending price order.
+    pub fn asks(&self) -> impl DoubleEndedIterator<Item = &Level> {
+        self.asks.values()
+    }
+
+    pub fn best_bid(&self) -> Option<&Level> {
+        self.bids.values().next_back()
+    }
+
+    pub fn best_ask(&self) -> Option<&Level> {
+        self.asks.values().next()
+    }
+
+    /// The mid price. `None` if either side is empty.
+    pub fn mid(&self) -> Option<f64> {
+        let (bid, ask) = self.best_prices()?;
+        Some((bid + ask) / 2.0)
+    }
+
+    /// Best ask minus best bid. `None` if either side is empty.
+    pub fn spread(&self) -> Option<f64> {
+        let (bid, ask) = self.best_prices()?;
+        Some(ask - bid)
+    }
+
+    /// The size at `price` on the bid (`Buy`) or ask (`Sell`) side. Zero if there is no level.
+    pub fn size_at(&self, side: Side, price: f64) -> f64 {
+        let level = price_key(price).and_then(|key| self.book(side)?.get(&key));
+        level.and_then(|l| l.size.to_f64()).unwrap_or_default()
+    }
+
+    /// The total size from the best price to `price` inclusive on the bid (`Buy`) or
+    /// ask (`Sell`) side.
+    pub fn depth_to(&self, side: Side, price: f64) -> f64 {
+        let Some(key) = price_key(price) else {
+            return 0.0;
+        };
+        let levels: Box<dyn Iterator<Item = &Level>> = match side {
+            Side::Buy => Box::new(self.bids.range(key..).map(|(_, l)| l)),
+            Side::Sell => Box::new(self.asks.range(..=key).map(|(_, l)| l)),
+            Side::Unknown(_) => return 0.0,
+        };
+        levels.filter_map(|l| l.size.to_f64()).sum()
+    }
+
+    /// Order book imbalance of the best `levels` levels, from -1 (all asks) to 1 (all bids).
+    /// `None` if both sides are empty.
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bid: f64 = self.sizes(Side::Buy).take(levels).map(|(_, s)| s).sum();
+        let ask: f64 = self.sizes(Side::Sell).take(levels).map(|(_, s)| s).sum();
+        let total = bid + ask;
+        (total > 0.0).then(|| (bid - ask) / total)
+    }
+
+    /// The mid price weighted by the size of the best bid and ask, which leans towards
+    /// the side with less size. `None` if either side is empty.
+    pub fn microprice(&self) -> Option<f64> {
+        let (bid, bid_size) = self.sizes(Side::Buy).next()?;
+        let (ask, ask_size) = self.sizes(Side::Sell).next()?;
+        let total = bid_size + ask_size;
+        (total > 0.0).then(|| (bid * ask_size + ask * bid_size) / total)
+    }
+
+    /// The total size within `bps` basis points of the best price on the bid (`Buy`) or
+    /// ask (`Sell`) side.
+    pub fn depth_within_bps(&self, side: Side, bps: f64) -> f64 {
+        let mut levels = self.sizes(side.clone()).peekable();
+        let Some(&(best, _)) = levels.peek() else {
+            return 0.0;
+        };
+        let offset = best * bps / 10_000.0;
+        levels
+            .take_while(|(price, _)| match side {
+                Side::Buy => *price >= best - offset,
+                _ => *price <= best + offset,
+            })
+            .map(|(_, size)| size)
+            .sum()
+    }
+
+    /// The volume weighted average price to fill a market order of `side` and `qty`.
+    /// A buy order fills against asks and a sell order against bids.
+    /// `None` if the book has not enough depth.
+    pub fn vwap(&self, side: Side, qty: f64) -> Option<f64> {
+        let levels = match side {
+            Side::Buy => self.sizes(Side::Sell),
+            Side::Sell => self.sizes(Side::Buy),
+            Side::Unknown(_) => return None,
+        };
+        let mut remaining = qty;
+        let mut notional = 0.0;
+        for (price, size) in levels {
+            let filled = size.min(remaining);
+            notional += filled * price;
+            remaining -= filled;
+            if remaining <= 0.0 {
+                return (qty > 0.0).then(|| notional / qty);
+            }
+        }
+        None
+    }
+
+    /// The expected slippage (bps) of a market order of `side` and `qty` from the mid price.
+    /// `None` if the book has not enough depth.
+    pub fn slippage(&self, side: Side, qty: f64) -> Option<f64> {
+        let mid = self.mid()?;
+        let vwap = self.vwap(side.clone(), qty)?;
+        let slippage = match side {
+            Side::Buy => vwap - mid,
+            _ => mid - vwap,
+        };
+        Some(slippage / mid * 10_000.0)
+    }
+
+    /// The (price, size) of levels on the bid (`Buy`) or ask (`Sell`) side from the best price.
+    fn sizes(&self, side: Side) -> Box<dyn Iterator<Item = (f64, f64)> + '_> {
+        let levels: Box<dyn Iterator<Item = &Level>> = match side {
+            Side::Buy => Box::new(self.bids()),
+            Side::Sell => Box::new(self.asks()),
+            Side::Unknown(_) => return Box::new(std::iter::empty()),
+        };
+        Box::new(levels.filter_map(|l| Some((l.price.to_f64()?, l.size.to_f64()?))))
+    }
+
+    fn book(&self, side: Side) -> Option<&BTreeMap<PriceKey, Level>> {
+        match side {
+            Side::Buy => Some(&self.bids),
+            Side::Sell => Some(&self.asks),
+            Side::Unknown(_) => None,
+        }
+    }
+
+    fn best_prices(&self) -> Option<(f64, f64)> {
+        let bid = self.best_bid()?.price.to_f64()?;
+        let ask = self.best_ask()?.price.to_f64()?;
+        Some((bid, ask))
+    }
+}
+
+/// Insert or replace the levels of `items`, removing the ones of zero size.
+fn update(book: &mut BTreeMap<PriceKey, Level>, items: &[OrderbookItem]) -> Result<()> {
+    for OrderbookItem { price, size, .. } in items {
+        let key = level_key(price)
+            .ok_or_else(|| BybitError::InvalidResponse(format!("invalid price: {}", price)))?;
+        if size.to_f64() == Some(0.0) {
+            book.remove(&key);
+        } else {
+            book.insert(
+                key,
+                Level {
+                    price: NumOwned::from(*price),
+                    size: NumOwned::from(*size),
+                },
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The key of a book in [`OrderbookManager`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BookKey {
+    pub category: Category,
+    pub symbol: String,
+    pub depth: u16,
+}
+
+impl BookKey {
+    /// `depth` is one of the `OrderbookDepth` of the websocket api clients, or its number of levels.
+    pub fn new<S: Into<String>, D: Into<u16>>(category: Category, symbol: S, depth: D) -> Self {
+        Self {
+            category,
+            symbol: symbol.into(),
+            depth: depth.into(),
+        }
+    }
+}
+
+/// The best bid and ask of a book, published by [`OrderbookManager`] when either changes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TopOfBook {
+    pub bid: Option<Level>,
+    pub ask: Option<Level>,
+    /// The update ID of the message which changed the top of book.
+    pub update_id: u64,
+    /// The timestamp of the message which changed the top of book.
+    pub ts: Option<Timestamp>,
+}
+
+struct Entry {
+    book: RwLock<LocalOrderbook>,
+    top: watch::Sender<TopOfBook>,
+}
+
+/// Local orderbooks of many symbols and depths, keyed by [`BookKey`].
+///
+/// The manager is cheap to clone and shared between the callbacks which apply frames
+/// and the tasks which query the books.
+#[derive(Clone, Default)]
+pub struct OrderbookManager {
+    books: Arc<RwLock<HashMap<BookKey, Arc<Entry>>>>,
+    resubscribers: HashMap<Category, Resubscriber>,
+}
+
+impl OrderbookManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resubscribe the books of `category` with `resubscriber` when they get out of sync.
+    /// See [`LocalOrderbook::resubscriber`].
+    pub fn resubscriber(mut self, category: Category, resubscriber: Resubscriber) -> Self {
+        self.resubscribers.insert(category, resubscriber);
+        self
+    }
+
+    /// Route an `orderbook.{depth}.{symbol}` frame of `category` to its book, creating the
+    /// book on the first frame, and publish the top of book if it changed.
+    pub fn apply(&self, category: Category, res: &BasePublicResponse<Orderbook>) -> Result<()> {
+        let depth = res
+            .topic
+            .strip_prefix("orderbook.")
+            .and_then(|s| s.split('.').next())
+            .and_then(|s| s.parse::<u16>().ok())
+            .ok_or_else(|| BybitError::InvalidResponse(format!("invalid topic: {}", res.topic)))?;
+        let entry = self.entry(BookKey::new(category, res.data.s, depth));
+
+        let mut book = entry.book.write().unwrap();
+        let applied = book.apply(res);
+        let top = TopOfBook {
+            bid: book.best_bid().cloned(),
+            ask: book.best_ask().cloned(),
+            update_id: book.update_id(),
+            ts: book.ts(),
+        };
+        drop(book);
+        entry.top.send_if_modified(|last| {
+            let modified = last.bid != top.bid || last.ask != top.ask;
+            if modified {
+                *last = top;
+            }
+            modified
+        });
+        applied
+    }
+
+    /// Call `f` with the book of `key` under a read lock. `None` if no frame of `key` was applied.
+    pub fn read<F, R>(&self, key: &BookKey, f: F) -> Option<R>
+    where
+        F: FnOnce(&LocalOrderbook) -> R,
+    {
+        let entry = self.books.read().unwrap().get(key).cloned()?;
+        let book = entry.book.read().unwrap();
+        Some(f(&book))
+    }
+
+    /// The current top of book of `key`.
+    pub fn top_of_book(&self, key: &BookKey) -> Option<TopOfBook> {
+        let entry = self.books.read().unwrap().get(key).cloned()?;
+        let top = entry.top.borrow().clone();
+        Some(top)
+    }
+
+    /// Watch the top of book of `key`. It can be called before the first frame arrives.
+    pub fn subscribe(&self, key: BookKey) -> watch::Receiver<TopOfBook> {
+        self.entry(key).top.subscribe()
+    }
+
+    /// The keys of all books.
+    pub fn keys(&self) -> Vec<BookKey> {
+        self.books.read().unwrap().keys().cloned().collect()
+    }
+
+    fn entry(&self, key: BookKey) -> Arc<Entry> {
+        if let Some(entry) = self.books.read().unwrap().get(&key) {
+            return entry.clone();
+        }
+        let mut books = self.books.write().unwrap();
+        books
+            .entry(key)
+            .or_insert_with_key(|key| {
+                let mut book = LocalOrderbook::new(key.symbol.as_str());
+                if let Some(resubscriber) = self.resubscribers.get(&key.category) {
+                    book = book.resubscriber(resubscriber.clone());
+                }
+                Arc::new(Entry {
+                    book: RwLock::new(book),
+                    top: watch::channel(TopOfBook::default()).0,
+                })
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ws::spot::OrderbookDepth;
+    use std::sync::Mutex;
+
+    fn apply(book: &mut LocalOrderbook, content: &str) -> Result<()> {
+        let res: BasePublicResponse<Orderbook> = serde_json::from_str(content).unwrap();
+        book.apply(&res)
+    }
+
+    #[test]
+    fn test_local_orderbook() {
+        let mut book = LocalOrderbook::new("BTCUSDT");
+        let delta = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967467,"data":{"s":"BTCUSDT","b":[["30247.20","0"],["30247.00","1.5"]],"a":[["30248.70","0"]],"u":178,"seq":1001}}"#;
+        assert!(matches!(
+            apply(&mut book, delta),
+            Err(BybitError::OrderbookNotSynced(_))
+        ));
+
+        apply(&mut book, r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["30247.20","30.028"],["30245.40","0.224"]],"a":[["30248.70","0.803"],["30249.30","0.100"]],"u":177,"seq":1000}}"#).unwrap();
+        assert!(book.is_synced());
+        assert_eq!(book.spread(), Some(30248.70 - 30247.20));
+
+        apply(&mut book, delta).unwrap();
+        let bids: Vec<f64> = book.bids().filter_map(|l| l.price.to_f64()).collect();
+        assert_eq!(bids, vec![30247.0, 30245.4]);
+        assert_eq!(book.best_ask().unwrap().price.to_f64(), Some(30249.3));
+        assert_eq!(book.mid(), Some((30247.0 + 30249.3) / 2.0));
+        assert_eq!(book.size_at(Side::Buy, 30247.0), 1.5);
+        assert_eq!(book.size_at(Side::Buy, 30247.2), 0.0);
+        assert!((book.depth_to(Side::Buy, 30245.4) - 1.724).abs() < 1e-9);
+        assert_eq!(book.depth_to(Side::Sell, 30248.0), 0.0);
+
+        let gap = delta.replace("178", "180").replace("1001", "1003");
+        assert!(matches!(
+            apply(&mut book, &gap),
+            Err(BybitError::OrderbookGap {
+                expected: 179,
+                received: 180,
+                ..
+            })
+        ));
+        assert!(!book.is_synced());
+
+        // Snapshot due to the restart of the service.
+        apply(&mut book, r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967470,"data":{"s":"BTCUSDT","b":[["30240.00","1"]],"a":[],"u":1,"seq":1004}}"#).unwrap();
+        assert!(book.is_synced());
+        assert_eq!(book.bids().count(), 1);
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.spread(), None);
+    }
+
+    #[test]
+    fn test_analytics() {
+        let mut book = LocalOrderbook::new("BTCUSDT");
+        apply(&mut book, r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["100.0","3"],["99.9","1"],["99.0","4"]],"a":[["100.2","1"],["100.3","2"],["101.0","5"]],"u":1,"seq":1000}}"#).unwrap();
+
+        let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+        assert!(close(book.imbalance(1), 0.5));
+        assert!(close(book.imbalance(2), (4.0 - 3.0) / 7.0));
+        assert!(close(book.microprice(), (100.0 * 1.0 + 100.2 * 3.0) / 4.0));
+        assert_eq!(book.depth_within_bps(Side::Buy, 10.0), 4.0);
+        assert_eq!(book.depth_within_bps(Side::Sell, 10.0), 3.0);
+        assert!(close(book.vwap(Side::Buy, 2.0), (100.2 + 100.3) / 2.0));
+        assert!(close(book.vwap(Side::Sell, 4.0), (300.0 + 99.9) / 4.0));
+        assert_eq!(book.vwap(Side::Buy, 9.0), None);
+        assert!(close(
+            book.slippage(Side::Buy, 2.0),
+            ((100.2 + 100.3) / 2.0 - 100.1) / 100.1 * 10_000.0
+        ));
+    }
+
+    #[test]
+    fn test_resync() {
+        let resubscriber = Resubscriber::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut book = LocalOrderbook::new("BTCUSDT")
+            .resubscriber(resubscriber.clone())
+            .on_event(move |e| sink.lock().unwrap().push(e));
+
+        let snapshot = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["30247.20","30.028"]],"a":[["30248.70","0.803"]],"u":177,"seq":1000}}"#;
+        apply(&mut book, snapshot).unwrap();
+        let gap = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967467,"data":{"s":"BTCUSDT","b":[],"a":[],"u":179,"seq":1001}}"#;
+        assert!(apply(&mut book, gap).is_err());
+        assert!(apply(&mut book, gap).is_err());
+        assert!(!book.is_synced());
+        assert_eq!(resubscriber.take(), vec!["orderbook.50.BTCUSDT".to_owned()]);
+
+        apply(&mut book, &snapshot.replace("177", "300")).unwrap();
+        assert!(book.is_synced());
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                OrderbookEvent::Resync {
+                    symbol: "BTCUSDT".to_owned(),
+                    topic: "orderbook.50.BTCUSDT".to_owned(),
+                },
+                OrderbookEvent::Resynced {
+                    symbol: "BTCUSDT".to_owned(),
+                    update_id: 300,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manager() {
+        let manager = OrderbookManager::new();
+        let key = BookKey::new(Category::Spot, "BTCUSDT", OrderbookDepth::Level1);
+        let mut rx = manager.subscribe(key.clone());
+
+        let content = r#"{"topic":"orderbook.1.BTCUSDT","type":"snapshot","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["30247.20","30.028"]],"a":[["30248.70","0.803"]],"u":177,"seq":1000}}"#;
+        let res: BasePublicResponse<Orderbook> = serde_json::from_str(content).unwrap();
+        manager.apply(Category::Spot, &res).unwrap();
+        let level50 = content.replace("orderbook.1.", "orderbook.50.");
+        let res: BasePublicResponse<Orderbook> = serde_json::from_str(&level50).unwrap();
+        manager.apply(Category::Spot, &res).unwrap();
+
+        assert_eq!(manager.keys().len(), 2);
+        assert!(rx.has_changed().unwrap());
+        let top = rx.borrow_and_update().clone();
+        assert_eq!(top.update_id, 177);
+        assert_eq!(top.bid.unwrap().price.to_f64(), Some(30247.2));
+
+        // The same top of book is not published again.
+        let content = content.replace("177", "178");
+        let res: BasePublicResponse<Orderbook> = serde_json::from_str(&content).unwrap();
+        manager.apply(Category::Spot, &res).unwrap();
+        assert!(!rx.has_changed().unwrap());
+        assert_eq!(manager.read(&key, |book| book.update_id()), Some(178));
+        assert_eq!(
+            manager.top_of_book(&BookKey::new(Category::Linear, "BTCUSDT", 1u16)),
+            None
+        );
+    }
+}
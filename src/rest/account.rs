@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::RestApiClient;
+use crate::error::Result;
+use crate::num::{NumOwned, OptNumOwned};
+
+/// A coin and amount to apply for in demo trading.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoFund {
+    /// Coin name, e.g. `USDT`, `BTC`, `ETH`, `USDC`.
+    pub coin: String,
+    /// Amount, e.g. `"100000"`.
+    pub amount_str: String,
+}
+
+impl DemoFund {
+    pub fn new<S: AsRef<str>>(coin: S, amount: S) -> Self {
+        Self {
+            coin: coin.as_ref().to_owned(),
+            amount_str: amount.as_ref().to_owned(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DemoApplyMoney<'a> {
+    adjust_type: u8,
+    uta_demo_apply_money: &'a [DemoFund],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct WalletBalanceQuery<'a> {
+    account_type: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+struct WalletBalances {
+    list: Vec<WalletBalance>,
+}
+
+/// The wallet balance of an account. The account-wide fields are empty in non-unified mode.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBalance {
+    /// Account type. `UNIFIED`, `CONTRACT`.
+    pub account_type: String,
+    /// Initial Margin Rate.
+    #[serde(rename = "accountIMRate")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub account_im_rate: OptNumOwned,
+    /// Maintenance Margin Rate.
+    #[serde(rename = "accountMMRate")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub account_mm_rate: OptNumOwned,
+    /// Equity of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_equity: OptNumOwned,
+    /// Wallet Balance of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_wallet_balance: OptNumOwned,
+    /// Margin Balance of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_margin_balance: OptNumOwned,
+    /// Available Balance of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_available_balance: OptNumOwned,
+    /// Unrealised P&L of perpetuals of account converted to usd.
+    #[serde(rename = "totalPerpUPL")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_perp_upl: OptNumOwned,
+    /// Initial Margin of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_initial_margin: OptNumOwned,
+    /// Maintenance Margin of account converted to usd.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_maintenance_margin: OptNumOwned,
+    /// Coins.
+    pub coin: Vec<WalletBalanceCoin>,
+}
+
+/// The balance of a coin in [`WalletBalance`].
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletBalanceCoin {
+    /// Coin name, such as BTC, ETH, USDT, USDC.
+    pub coin: String,
+    /// Equity of current coin.
+    pub equity: NumOwned,
+    /// USD value of current coin.
+    pub usd_value: NumOwned,
+    /// Wallet balance of current coin.
+    pub wallet_balance: NumOwned,
+    /// Available amount to withdraw of current coin. It can be empty.
+    #[serde(default)]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub available_to_withdraw: OptNumOwned,
+    /// Pre-occupied margin for order.
+    #[serde(rename = "totalOrderIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_order_im: OptNumOwned,
+    /// Sum of initial margin of all positions + Pre-occupied liquidation fee.
+    #[serde(rename = "totalPositionIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_position_im: OptNumOwned,
+    /// Sum of maintenance margin for all positions.
+    #[serde(rename = "totalPositionMM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_position_mm: OptNumOwned,
+    /// Unrealised P&L.
+    pub unrealised_pnl: NumOwned,
+    /// Cumulative Realised P&L.
+    pub cum_realised_pnl: NumOwned,
+}
+
+impl RestApiClient {
+    /// Get the wallet balance of `account_type`, e.g. `UNIFIED`.
+    pub async fn wallet_balance<S: AsRef<str>>(
+        &self,
+        account_type: S,
+    ) -> Result<Vec<WalletBalance>> {
+        let query = WalletBalanceQuery {
+            account_type: account_type.as_ref(),
+        };
+        let balances: WalletBalances = self
+            .get_signed("/v5/account/wallet-balance", &query)
+            .await?;
+        Ok(balances.list)
+    }
+
+    /// Apply for demo trading funds. Only available with a demo trading api key
+    /// on a client built with `.demo()`.
+    pub async fn apply_demo_funds(&self, funds: &[DemoFund]) -> Result<()> {
+        let body = DemoApplyMoney {
+            adjust_type: 0,
+            uta_demo_apply_money: funds,
+        };
+        let _: Value = self
+            .post_signed("/v5/account/demo-apply-money", &body)
+            .await?;
+        Ok(())
+    }
+}
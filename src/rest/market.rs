@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use super::RestApiClient;
+use crate::error::Result;
+use crate::num::NumOwned;
+use crate::timestamp::Timestamp;
+use crate::{Category, KlineInterval};
+
+/// The Bybit server time.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerTime {
+    /// Server timestamp (s).
+    pub time_second: String,
+    /// Server timestamp (ns).
+    pub time_nano: String,
+}
+
+impl ServerTime {
+    /// Server timestamp (ms).
+    pub fn millis(&self) -> Option<u128> {
+        self.time_nano.parse::<u128>().ok().map(|ns| ns / 1_000_000)
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct InstrumentsQuery<'a> {
+    category: &'a str,
+    base_coin: &'a str,
+    limit: u16,
+}
+
+#[derive(Deserialize, Debug)]
+struct Instrument {
+    symbol: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct KlineQuery<'a> {
+    category: Category,
+    symbol: &'a str,
+    interval: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<u64>,
+    limit: u16,
+}
+
+#[derive(Deserialize, Debug)]
+struct Klines {
+    list: Vec<MarketKline>,
+}
+
+/// A historical kline, returned as a `[start, open, high, low, close, volume, turnover]` array.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MarketKline {
+    /// The start timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub start: Timestamp,
+    /// Open price.
+    pub open: NumOwned,
+    /// Highest price.
+    pub high: NumOwned,
+    /// Lowest price.
+    pub low: NumOwned,
+    /// Close price.
+    pub close: NumOwned,
+    /// Trade volume.
+    pub volume: NumOwned,
+    /// Turnover.
+    pub turnover: NumOwned,
+}
+
+impl RestApiClient {
+    /// Get the Bybit server time.
+    pub async fn server_time(&self) -> Result<ServerTime> {
+        self.get("/v5/market/time", &()).await
+    }
+
+    /// Get the symbols of all option instruments of `base_coin`, e.g. `BTC-29DEC23-40000-C`.
+    pub async fn option_symbols<S: AsRef<str>>(&self, base_coin: S) -> Result<Vec<String>> {
+        let query = InstrumentsQuery {
+            category: "option",
+            base_coin: base_coin.as_ref(),
+            limit: 1000,
+        };
+        let instruments: Vec<Instrument> = self
+            .get_pages("/v5/market/instruments-info", &query, false)
+            .await?;
+        Ok(instruments.into_iter().map(|i| i.symbol).collect())
+    }
+
+    /// Get historical klines in descending order of the start time, at most `limit` (1000) of them.
+    /// `start` and `end` are timestamps (ms).
+    pub async fn klines<S: AsRef<str>>(
+        &self,
+        category: Category,
+        symbol: S,
+        interval: KlineInterval,
+        start: Option<u64>,
+        end: Option<u64>,
+        limit: u16,
+    ) -> Result<Vec<MarketKline>> {
+        let query = KlineQuery {
+            category,
+            symbol: symbol.as_ref(),
+            interval: interval.into(),
+            start,
+            end,
+            limit,
+        };
+        let klines: Klines = self.get("/v5/market/kline", &query).await?;
+        Ok(klines.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::ToMillis;
+
+    #[test]
+    fn test_klines() {
+        let content = r#"{"category":"inverse","symbol":"BTCUSD","list":[["1670608800000","17071","17073","17027","17055.5","268611","15.74462667"],["1670605200000","17071.5","17071.5","17061","17071","4177","0.24469757"]]}"#;
+        let klines: Klines = serde_json::from_str(content).unwrap();
+        assert_eq!(klines.list.len(), 2);
+        assert_eq!(klines.list[0].start.to_millis(), 1670608800000);
+        assert_eq!(klines.list[1].close.to_string(), "17071");
+    }
+}
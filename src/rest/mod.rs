@@ -0,0 +1,323 @@
+pub mod account;
+pub mod market;
+pub mod order;
+pub mod position;
+pub mod spread;
+
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::credentials::Credentials;
+use crate::error::{BybitError, Result};
+use crate::time_sync::Clock;
+use crate::Environment;
+
+/// The common envelope of all REST responses.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    ret_code: i32,
+    ret_msg: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Response<T> {
+    result: T,
+}
+
+/// A page of a list endpoint.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(default)]
+    next_page_cursor: String,
+    list: Vec<T>,
+}
+
+/// The query of a list endpoint with the cursor of the page to fetch.
+#[derive(Serialize, Debug)]
+struct PageQuery<'a, Q> {
+    #[serde(flatten)]
+    query: &'a Q,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    cursor: &'a str,
+}
+
+/// A REST api client.
+#[derive(Clone)]
+pub struct RestApiClient {
+    uri: String,
+    client: Client,
+    credentials: Option<Credentials>,
+    recv_window: u64,
+    clock: Clock,
+}
+
+impl RestApiClient {
+    /// Get a builder for building REST api client.
+    pub fn builder() -> RestApiClientBuilder {
+        RestApiClientBuilder::new()
+    }
+
+    /// The clock which signs requests.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    async fn get<T, Q>(&self, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + ?Sized,
+    {
+        let res = self
+            .client
+            .get(format!("{}{}", self.uri, path))
+            .query(query)
+            .send()
+            .await?;
+        parse(&res.text().await?)
+    }
+
+    async fn get_signed<T, Q>(&self, path: &str, query: &Q) -> Result<T>
+    where
+        T: DeserializeOwned,
+        Q: Serialize,
+    {
+        let query = serde_qs::to_string(query)?;
+        let headers = self.sign(&query)?;
+        let res = self
+            .client
+            .get(format!("{}{}?{}", self.uri, path, query))
+            .headers(headers)
+            .send()
+            .await?;
+        parse(&res.text().await?)
+    }
+
+    async fn post_signed<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize,
+    {
+        let body = serde_json::to_string(body)?;
+        let headers = self.sign(&body)?;
+        let res = self
+            .client
+            .post(format!("{}{}", self.uri, path))
+            .headers(headers)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?;
+        parse(&res.text().await?)
+    }
+
+    /// Fetch every page of a list endpoint, following `nextPageCursor`.
+    async fn get_pages<T, Q>(&self, path: &str, query: &Q, signed: bool) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+        Q: Serialize,
+    {
+        let mut list = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let query = PageQuery {
+                query,
+                cursor: &cursor,
+            };
+            let page: Page<T> = if signed {
+                self.get_signed(path, &query).await?
+            } else {
+                self.get(path, &query).await?
+            };
+            list.extend(page.list);
+            if page.next_page_cursor.is_empty() {
+                return Ok(list);
+            }
+            cursor = page.next_page_cursor;
+        }
+    }
+
+    /// Create the auth headers. The signed payload is `timestamp + api_key + recv_window + params`,
+    /// where `params` is the query string for GET and the JSON body for POST.
+    fn sign(&self, params: &str) -> Result<HeaderMap> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or(BybitError::MissingCredentials)?;
+        let timestamp = self.clock.timestamp().to_string();
+        let recv_window = self.recv_window.to_string();
+        let payload = format!(
+            "{}{}{}{}",
+            timestamp,
+            credentials.api_key(),
+            recv_window,
+            params
+        );
+        let signature = credentials.sign(&payload)?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            ("X-BAPI-API-KEY", credentials.api_key()),
+            ("X-BAPI-TIMESTAMP", &timestamp),
+            ("X-BAPI-RECV-WINDOW", &recv_window),
+            ("X-BAPI-SIGN", &signature),
+        ] {
+            let value =
+                HeaderValue::from_str(value).map_err(|e| BybitError::InvalidKey(e.to_string()))?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Parse `result` of a response. The result of a failed request is usually `{}`,
+/// so the return code is checked before parsing the result.
+fn parse<T: DeserializeOwned>(content: &str) -> Result<T> {
+    let envelope: Envelope = serde_json::from_str(content)?;
+    if envelope.ret_code != 0 {
+        return Err(BybitError::ApiError {
+            code: envelope.ret_code,
+            msg: envelope.ret_msg,
+        });
+    }
+    let res: Response<T> = serde_json::from_str(content)?;
+    Ok(res.result)
+}
+
+pub struct RestApiClientBuilder {
+    uri: String,
+    recv_window: u64,
+    clock: Clock,
+}
+
+impl Default for RestApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RestApiClientBuilder {
+    /// Create a new `RestApiClientBuilder`. Base URI is set to the mainnet.
+    pub fn new() -> Self {
+        Self {
+            uri: Environment::Mainnet.rest(),
+            recv_window: 5000,
+            clock: Clock::default(),
+        }
+    }
+
+    /// Change base URI to the testnet.
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change base URI to the demo trading.
+    pub fn demo(self) -> Self {
+        self.env(Environment::Demo)
+    }
+
+    /// Change base URI to the REST api of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.rest();
+        self
+    }
+
+    /// Set base URI to the URI specified.
+    pub fn uri<S: AsRef<str>>(mut self, uri: S) -> Self {
+        self.uri = uri.as_ref().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Set the receive window (ms) of signed requests. Default is 5000.
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// Sign requests with `clock`, e.g. the clock of another client synchronised by
+    /// [`TimeSync`](crate::TimeSync).
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Build a REST api client for public endpoints only.
+    pub fn build(self) -> RestApiClient {
+        self.build_inner(None)
+    }
+
+    /// Build a REST api client with api key and secret key.
+    pub fn build_with_credentials<S: AsRef<str>>(self, api_key: S, secret: S) -> RestApiClient {
+        self.build_with(Credentials::hmac(api_key, secret))
+    }
+
+    /// Build a REST api client with HMAC or RSA credentials.
+    pub fn build_with(self, credentials: Credentials) -> RestApiClient {
+        self.build_inner(Some(credentials))
+    }
+
+    fn build_inner(self, credentials: Option<Credentials>) -> RestApiClient {
+        RestApiClient {
+            uri: self.uri,
+            client: Client::new(),
+            credentials,
+            recv_window: self.recv_window,
+            clock: self.clock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use market::ServerTime;
+
+    #[test]
+    fn test_parse() {
+        let content = r#"{"retCode":0,"retMsg":"OK","result":{"timeSecond":"1688639403","timeNano":"1688639403423213947"},"retExtInfo":{},"time":1688639403423}"#;
+        let time: ServerTime = parse(content).unwrap();
+        assert_eq!(time.millis(), Some(1688639403423));
+
+        let content = r#"{"retCode":10004,"retMsg":"error sign!","result":{},"retExtInfo":{},"time":1688639403423}"#;
+        match parse::<ServerTime>(content) {
+            Err(BybitError::ApiError { code, .. }) => assert_eq!(code, 10004),
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_page_query() {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Query {
+            category: crate::Category,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            settle_coin: Option<&'static str>,
+            limit: u16,
+        }
+        let query = Query {
+            category: crate::Category::Linear,
+            settle_coin: None,
+            limit: 50,
+        };
+        let first = PageQuery {
+            query: &query,
+            cursor: "",
+        };
+        assert_eq!(
+            serde_qs::to_string(&first).unwrap(),
+            "category=linear&limit=50"
+        );
+        let next = PageQuery {
+            query: &query,
+            cursor: "page_args%3D2",
+        };
+        assert_eq!(
+            serde_qs::to_string(&next).unwrap(),
+            "category=linear&limit=50&cursor=page_args%253D2"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{Page, RestApiClient};
+use crate::enums::{
+    empty_as_none, Category, ExecType, OrderStatus, OrderType, Side, StopOrderType,
+};
+use crate::error::Result;
+use crate::num::{NumOwned, OptNumOwned};
+use crate::timestamp::Timestamp;
+use crate::ws::dcp::DcpProduct;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DcpWindow {
+    product: DcpProduct,
+    time_window: u32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OrderQuery<'a> {
+    category: Category,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    settle_coin: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u16>,
+}
+
+impl OrderQuery<'_> {
+    fn new(category: Category) -> Self {
+        Self {
+            category,
+            settle_coin: None,
+            order_id: None,
+            start_time: None,
+            end_time: None,
+            limit: None,
+        }
+    }
+}
+
+/// An order returned by the open orders and order history endpoints.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderInfo {
+    /// Order ID.
+    pub order_id: String,
+    /// User customised order ID.
+    pub order_link_id: String,
+    /// Symbol name.
+    pub symbol: String,
+    /// Order price.
+    pub price: NumOwned,
+    /// Order qty.
+    pub qty: NumOwned,
+    /// Side. `Buy`, `Sell`.
+    pub side: Side,
+    /// Position index. Used to identify positions in different position modes.
+    pub position_idx: u8,
+    /// Order status.
+    pub order_status: OrderStatus,
+    /// Reject reason.
+    pub reject_reason: String,
+    /// Average filled price. If unfilled, it is "".
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub avg_price: OptNumOwned,
+    /// Cumulative executed order qty.
+    pub cum_exec_qty: NumOwned,
+    /// Cumulative executed order value.
+    pub cum_exec_value: NumOwned,
+    /// Cumulative executed trading fee.
+    pub cum_exec_fee: NumOwned,
+    /// Order type. `Market`, `Limit`.
+    pub order_type: OrderType,
+    /// Stop order type.
+    #[serde(with = "empty_as_none")]
+    pub stop_order_type: Option<StopOrderType>,
+    /// Order created timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
+    /// Order updated timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
+}
+
+/// An execution returned by the execution history endpoint.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionInfo {
+    /// Symbol name.
+    pub symbol: String,
+    /// Order ID.
+    pub order_id: String,
+    /// User customized order ID.
+    pub order_link_id: String,
+    /// Side. `Buy`, `Sell`.
+    pub side: Side,
+    /// Order price.
+    pub order_price: NumOwned,
+    /// Order qty.
+    pub order_qty: NumOwned,
+    /// The remaining qty not executed.
+    pub leaves_qty: NumOwned,
+    /// Order type. `Market`, `Limit`.
+    pub order_type: OrderType,
+    /// Stop order type. If the order is not stop order, it is empty.
+    #[serde(with = "empty_as_none")]
+    pub stop_order_type: Option<StopOrderType>,
+    /// Executed trading fee.
+    pub exec_fee: NumOwned,
+    /// Execution ID.
+    pub exec_id: String,
+    /// Execution price.
+    pub exec_price: NumOwned,
+    /// Execution qty.
+    pub exec_qty: NumOwned,
+    /// Executed type.
+    pub exec_type: ExecType,
+    /// Executed timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub exec_time: Timestamp,
+    /// Is maker order. true: maker, false: taker.
+    pub is_maker: bool,
+}
+
+impl RestApiClient {
+    /// Set the DCP (disconnect-cancel-all) time window (s) of `product`, in [3, 300].
+    /// Once all private connections are lost for the time window, all open orders
+    /// of `product` are cancelled by the server.
+    pub async fn set_dcp_window(&self, product: DcpProduct, time_window: u32) -> Result<()> {
+        let body = DcpWindow {
+            product,
+            time_window,
+        };
+        let _: Value = self
+            .post_signed("/v5/order/disconnected-cancel-all", &body)
+            .await?;
+        Ok(())
+    }
+
+    /// Get all open orders of `category`. `settle_coin` is required for `linear` and
+    /// `inverse`, e.g. `USDT`.
+    pub async fn open_orders(
+        &self,
+        category: Category,
+        settle_coin: Option<&str>,
+    ) -> Result<Vec<OrderInfo>> {
+        let query = OrderQuery {
+            settle_coin,
+            limit: Some(50),
+            ..OrderQuery::new(category)
+        };
+        self.get_pages("/v5/order/realtime", &query, true).await
+    }
+
+    /// Get an order of `category` from the order history, whatever its status.
+    pub async fn order_history(
+        &self,
+        category: Category,
+        order_id: &str,
+    ) -> Result<Option<OrderInfo>> {
+        let query = OrderQuery {
+            order_id: Some(order_id),
+            ..OrderQuery::new(category)
+        };
+        let page: Page<OrderInfo> = self.get_signed("/v5/order/history", &query).await?;
+        Ok(page.list.into_iter().next())
+    }
+
+    /// Get the executions of `category` from `start_time` to `end_time` (ms), both
+    /// inclusive. The range can not exceed 7 days.
+    pub async fn executions(
+        &self,
+        category: Category,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<ExecutionInfo>> {
+        let query = OrderQuery {
+            start_time: Some(start_time),
+            end_time: Some(end_time),
+            limit: Some(100),
+            ..OrderQuery::new(category)
+        };
+        self.get_pages("/v5/execution/list", &query, true).await
+    }
+}
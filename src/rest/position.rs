@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use super::RestApiClient;
+use crate::enums::{empty_as_none, Side};
+use crate::error::{BybitError, Result};
+use crate::num::{NumOwned, OptNumOwned};
+use crate::timestamp::Timestamp;
+use crate::Category;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PositionQuery<'a> {
+    category: Category,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    settle_coin: Option<&'a str>,
+    limit: u16,
+}
+
+/// A position returned by the position list endpoint.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionInfo {
+    /// Position index. Used to identify positions in different position modes.
+    pub position_idx: u8,
+    /// Symbol name.
+    pub symbol: String,
+    /// Position side. `None` for an empty position.
+    #[serde(with = "empty_as_none")]
+    pub side: Option<Side>,
+    /// Position size.
+    pub size: NumOwned,
+    /// Average entry price.
+    pub avg_price: NumOwned,
+    /// Position value.
+    pub position_value: NumOwned,
+    /// Position leverage. Empty for portfolio margin accounts.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leverage: OptNumOwned,
+    /// Mark price.
+    pub mark_price: NumOwned,
+    /// Position liquidation price. It can be empty.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub liq_price: OptNumOwned,
+    /// Initial margin. Empty for portfolio margin accounts.
+    #[serde(rename = "positionIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub position_im: OptNumOwned,
+    /// Maintenance margin. Empty for portfolio margin accounts.
+    #[serde(rename = "positionMM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub position_mm: OptNumOwned,
+    /// Unrealised PnL.
+    pub unrealised_pnl: NumOwned,
+    /// Cumulative realised PnL.
+    pub cum_realised_pnl: NumOwned,
+    /// Position created timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
+    /// Position updated timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
+}
+
+impl RestApiClient {
+    /// Get all positions of `category`. `settle_coin` is required for `linear` and
+    /// `inverse`, e.g. `USDT`. Fail with `InvalidCategory` if it is missing.
+    pub async fn positions(
+        &self,
+        category: Category,
+        settle_coin: Option<&str>,
+    ) -> Result<Vec<PositionInfo>> {
+        if settle_coin.is_none() && matches!(category, Category::Linear | Category::Inverse) {
+            return Err(BybitError::InvalidCategory(format!(
+                "{} without settle coin",
+                category.as_str()
+            )));
+        }
+        let query = PositionQuery {
+            category,
+            settle_coin,
+            limit: 200,
+        };
+        self.get_pages("/v5/position/list", &query, true).await
+    }
+}
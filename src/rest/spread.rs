@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::RestApiClient;
+use crate::enums::{OrderType, Side, TimeInForce};
+use crate::error::Result;
+use crate::order::OrderResult;
+
+/// Parameters of a spread order. Spread prices can be negative.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadOrderRequest {
+    /// Spread symbol name, e.g. `SOLUSDT_SOL/USDT`.
+    pub symbol: String,
+    /// Side.
+    pub side: Side,
+    /// Order type.
+    pub order_type: OrderType,
+    /// Order qty.
+    pub qty: String,
+    /// Order price. Ignored by market orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    /// User customised order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+    /// Time in force.
+    pub time_in_force: TimeInForce,
+}
+
+impl SpreadOrderRequest {
+    /// Create a GTC limit spread order request.
+    pub fn limit<S: AsRef<str>>(symbol: S, side: Side, qty: S, price: S) -> Self {
+        Self {
+            symbol: symbol.as_ref().to_owned(),
+            side,
+            order_type: OrderType::Limit,
+            qty: qty.as_ref().to_owned(),
+            price: Some(price.as_ref().to_owned()),
+            order_link_id: None,
+            time_in_force: TimeInForce::Gtc,
+        }
+    }
+
+    /// Create an IOC market spread order request.
+    pub fn market<S: AsRef<str>>(symbol: S, side: Side, qty: S) -> Self {
+        Self {
+            symbol: symbol.as_ref().to_owned(),
+            side,
+            order_type: OrderType::Market,
+            qty: qty.as_ref().to_owned(),
+            price: None,
+            order_link_id: None,
+            time_in_force: TimeInForce::Ioc,
+        }
+    }
+
+    /// Set the user customised order ID.
+    pub fn order_link_id<S: AsRef<str>>(mut self, order_link_id: S) -> Self {
+        self.order_link_id = Some(order_link_id.as_ref().to_owned());
+        self
+    }
+
+    /// Set the time in force.
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+}
+
+/// Parameters to amend a spread order. Either `order_id` or `order_link_id` is required.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadAmendRequest {
+    /// Spread symbol name.
+    pub symbol: String,
+    /// Order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    /// User customised order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+    /// New order qty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<String>,
+    /// New order price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SpreadCancelRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_link_id: Option<&'a str>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SpreadCancelAllRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<&'a str>,
+    cancel_all: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct SpreadCancelAllResult {
+    list: Vec<OrderResult>,
+}
+
+impl RestApiClient {
+    /// Place a spread order.
+    pub async fn create_spread_order(&self, req: &SpreadOrderRequest) -> Result<OrderResult> {
+        self.post_signed("/v5/spread/order/create", req).await
+    }
+
+    /// Amend an open spread order.
+    pub async fn amend_spread_order(&self, req: &SpreadAmendRequest) -> Result<OrderResult> {
+        self.post_signed("/v5/spread/order/amend", req).await
+    }
+
+    /// Cancel a spread order by order ID.
+    pub async fn cancel_spread_order<S: AsRef<str>>(&self, order_id: S) -> Result<OrderResult> {
+        let req = SpreadCancelRequest {
+            order_id: Some(order_id.as_ref()),
+            order_link_id: None,
+        };
+        self.post_signed("/v5/spread/order/cancel", &req).await
+    }
+
+    /// Cancel a spread order by user customised order ID.
+    pub async fn cancel_spread_order_by_link_id<S: AsRef<str>>(
+        &self,
+        order_link_id: S,
+    ) -> Result<OrderResult> {
+        let req = SpreadCancelRequest {
+            order_id: None,
+            order_link_id: Some(order_link_id.as_ref()),
+        };
+        self.post_signed("/v5/spread/order/cancel", &req).await
+    }
+
+    /// Cancel all open spread orders, or only those of `symbol`.
+    /// Return the orders cancelled.
+    pub async fn cancel_all_spread_orders(&self, symbol: Option<&str>) -> Result<Vec<OrderResult>> {
+        let req = SpreadCancelAllRequest {
+            symbol,
+            cancel_all: symbol.is_none(),
+        };
+        let res: SpreadCancelAllResult = self
+            .post_signed("/v5/spread/order/cancel-all", &req)
+            .await?;
+        Ok(res.list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_order_request() {
+        let req = SpreadOrderRequest::limit("SOLUSDT_SOL/USDT", Side::Buy, "2", "-0.5")
+            .order_link_id("1");
+        assert_eq!(
+            serde_json::to_string(&req).unwrap(),
+            r#"{"symbol":"SOLUSDT_SOL/USDT","side":"Buy","orderType":"Limit","qty":"2","price":"-0.5","orderLinkId":"1","timeInForce":"GTC"}"#
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Future tickers merged from the snapshots and deltas of the `tickers` topic.
+
+use std::collections::HashMap;
+
+use crate::timestamp::{Timestamp, ToMillis};
+use crate::ws::response::{BaseTickerPublicResponse, FutureTicker, FutureTickerStatic};
+
+/// Overwrite the fields of `$state` with the fields of `$delta` which are not `None`.
+macro_rules! merge {
+    ($state:expr, $delta:expr, $($field:ident),+ $(,)?) => {
+        $(
+            if let Some(v) = $delta.$field {
+                $state.$field = Some(v.into());
+            }
+        )+
+    };
+}
+
+struct TickerEntry {
+    ticker: FutureTickerStatic,
+    cs: u64,
+    ts: Timestamp,
+}
+
+/// The latest future tickers of many symbols.
+///
+/// A snapshot replaces the ticker of its symbol, and a delta overwrites the fields it
+/// contains. Messages whose cross sequence is older than the last applied one are
+/// ignored. Mark price, index price and funding updates may not advance the cross
+/// sequence, so a delta of the same cross sequence is applied if its timestamp is newer.
+#[derive(Default)]
+pub struct TickerState {
+    tickers: HashMap<String, TickerEntry>,
+}
+
+impl TickerState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a snapshot or delta and return the merged ticker.
+    /// `None` if the message is stale or a delta arrives before the snapshot of its symbol.
+    pub fn apply(
+        &mut self,
+        res: &BaseTickerPublicResponse<FutureTicker>,
+    ) -> Option<&FutureTickerStatic> {
+        let data = &res.data;
+        if res.type_ == "snapshot" {
+            if self.tickers.get(data.symbol).is_some_and(|e| res.cs < e.cs) {
+                return None;
+            }
+            let entry = TickerEntry {
+                ticker: FutureTickerStatic {
+                    symbol: data.symbol.to_owned(),
+                    ..Default::default()
+                },
+                cs: res.cs,
+                ts: res.ts,
+            };
+            self.tickers.insert(data.symbol.to_owned(), entry);
+        }
+
+        let entry = self.tickers.get_mut(data.symbol)?;
+        if res.type_ != "snapshot" {
+            if (res.cs, res.ts.to_millis()) <= (entry.cs, entry.ts.to_millis()) {
+                return None;
+            }
+            entry.cs = res.cs;
+            entry.ts = res.ts;
+        }
+        merge!(
+            entry.ticker,
+            data,
+            tick_direction,
+            price_24h_pcnt,
+            last_price,
+            prev_price_24h,
+            high_price_24h,
+            low_price_24h,
+            prev_price_1h,
+            mark_price,
+            index_price,
+            open_interest,
+            open_interest_value,
+            turnover_24h,
+            volume_24h,
+            next_funding_time,
+            funding_rate,
+            bid1_price,
+            bid1_size,
+            ask1_price,
+            ask1_size,
+            delivery_time,
+            basis_rate,
+            delivery_fee_rate,
+            predicted_delivery_price,
+        );
+        Some(&entry.ticker)
+    }
+
+    /// The merged ticker of `symbol`.
+    pub fn get(&self, symbol: &str) -> Option<&FutureTickerStatic> {
+        self.tickers.get(symbol).map(|e| &e.ticker)
+    }
+
+    /// The cross sequence of the last applied message of `symbol`.
+    pub fn cs(&self, symbol: &str) -> Option<u64> {
+        self.tickers.get(symbol).map(|e| e.cs)
+    }
+
+    /// The timestamp of the last applied message of `symbol`.
+    pub fn ts(&self, symbol: &str) -> Option<Timestamp> {
+        self.tickers.get(symbol).map(|e| e.ts)
+    }
+
+    /// All symbols with a ticker.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.tickers.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::ToF64;
+
+    fn apply<'a>(state: &'a mut TickerState, content: &str) -> Option<&'a FutureTickerStatic> {
+        let res: BaseTickerPublicResponse<FutureTicker> = serde_json::from_str(content).unwrap();
+        state.apply(&res)
+    }
+
+    #[test]
+    fn test_ticker_state() {
+        let mut state = TickerState::new();
+        let delta = r#"{"topic":"tickers.BTCUSDT","type":"delta","data":{"symbol":"BTCUSDT","bid1Price":"17215.40","bid1Size":"84.489","markPrice":"17217.33"},"cs":24987957,"ts":1673272861686}"#;
+        assert!(apply(&mut state, delta).is_none());
+
+        let snapshot = r#"{"topic":"tickers.BTCUSDT","type":"snapshot","data":{"symbol":"BTCUSDT","tickDirection":"PlusTick","price24hPcnt":"0.017103","lastPrice":"17216.00","prevPrice24h":"16926.50","highPrice24h":"17281.50","lowPrice24h":"16915.00","prevPrice1h":"17238.00","markPrice":"17217.33","indexPrice":"17227.36","openInterest":"68744.761","openInterestValue":"1183601235.91","turnover24h":"1570383121.943499","volume24h":"91705.276","nextFundingTime":"1673280000000","fundingRate":"-0.000212","bid1Price":"17215.50","bid1Size":"84.489","ask1Price":"17216.00","ask1Size":"83.020"},"cs":24987956,"ts":1673272861400}"#;
+        let ticker = apply(&mut state, snapshot).unwrap();
+        assert_eq!(ticker.last_price.to_f64(), Some(17216.0));
+
+        let ticker = apply(&mut state, delta).unwrap();
+        assert_eq!(ticker.bid1_price.to_f64(), Some(17215.4));
+        assert_eq!(ticker.ask1_price.to_f64(), Some(17216.0));
+        assert_eq!(ticker.funding_rate.to_f64(), Some(-0.000212));
+        assert_eq!(ticker.delivery_time, None);
+        assert_eq!(state.cs("BTCUSDT"), Some(24987957));
+
+        // Stale delta.
+        assert!(apply(&mut state, &delta.replace("17215.40", "1")).is_none());
+        assert_eq!(
+            state.get("BTCUSDT").unwrap().bid1_price.to_f64(),
+            Some(17215.4)
+        );
+
+        // A delta of the same cross sequence with a newer timestamp.
+        let mark = r#"{"topic":"tickers.BTCUSDT","type":"delta","data":{"symbol":"BTCUSDT","markPrice":"17218.00","indexPrice":"17228.00"},"cs":24987957,"ts":1673272861786}"#;
+        let ticker = apply(&mut state, mark).unwrap();
+        assert_eq!(ticker.mark_price.to_f64(), Some(17218.0));
+        assert_eq!(ticker.bid1_price.to_f64(), Some(17215.4));
+        assert_eq!(state.cs("BTCUSDT"), Some(24987957));
+        assert!(apply(&mut state, mark).is_none());
+    }
+}
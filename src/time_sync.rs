@@ -0,0 +1,162 @@
+use log::*;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::error::{BybitError, Result};
+use crate::rest::RestApiClient;
+use crate::util::millis;
+
+/// The Bybit server clock, i.e. the local clock compensated by an offset.
+///
+/// Clones share the offset. A client signs its requests with its own clock, so clients of
+/// different environments can be synchronised separately.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    offset: Arc<AtomicI64>,
+}
+
+impl Clock {
+    /// Offset (ms) to add to the local clock to get the server clock.
+    pub fn offset(&self) -> i64 {
+        self.offset.load(Ordering::Relaxed)
+    }
+
+    /// Set the offset (ms) to add to the local clock to get the server clock.
+    pub fn set_offset(&self, offset: i64) {
+        self.offset.store(offset, Ordering::Relaxed);
+    }
+
+    /// The current server timestamp (ms).
+    pub fn timestamp(&self) -> u128 {
+        (millis() as i128 + self.offset() as i128) as u128
+    }
+}
+
+/// A clock measurement against the Bybit server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSample {
+    /// Offset (ms) to add to the local clock to get the server clock.
+    pub offset: i64,
+    /// Round-trip latency (ms) of the time request.
+    pub latency: u64,
+}
+
+impl ClockSample {
+    /// Estimate the clock offset from the local send time, the server time and the local receive time.
+    ///
+    /// The server time is assumed to be taken half way through the round trip.
+    pub fn estimate(sent: u128, server: u128, received: u128) -> Self {
+        let latency = received.saturating_sub(sent);
+        let midpoint = sent + latency / 2;
+        Self {
+            offset: (server as i128 - midpoint as i128) as i64,
+            latency: latency as u64,
+        }
+    }
+}
+
+/// Synchronises the local clock with the Bybit server clock.
+///
+/// The estimated offset is applied to the [`Clock`] of the REST api client. Pass the
+/// same clock to the websocket api client builders to sign their requests with it.
+#[derive(Clone)]
+pub struct TimeSync {
+    client: RestApiClient,
+    samples: usize,
+    latency: Arc<AtomicU64>,
+}
+
+impl TimeSync {
+    /// Create a new `TimeSync` which queries the server time by `client`.
+    pub fn new(client: RestApiClient) -> Self {
+        Self {
+            client,
+            samples: 3,
+            latency: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Set the number of requests per sync. The sample with the lowest latency wins.
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// The clock which is synchronised, shared with the REST api client.
+    pub fn clock(&self) -> &Clock {
+        self.client.clock()
+    }
+
+    /// Round-trip latency (ms) of the last sync.
+    pub fn latency(&self) -> u64 {
+        self.latency.load(Ordering::Relaxed)
+    }
+
+    /// Measure the clock offset and apply it.
+    pub async fn sync(&self) -> Result<ClockSample> {
+        let mut best: Option<ClockSample> = None;
+        for _ in 0..self.samples {
+            let sample = self.sample().await?;
+            if best.is_none_or(|b| sample.latency < b.latency) {
+                best = Some(sample);
+            }
+        }
+        let best = best.expect("at least one sample");
+        self.clock().set_offset(best.offset);
+        self.latency.store(best.latency, Ordering::Relaxed);
+        debug!(
+            "Clock offset: {}ms, latency: {}ms",
+            best.offset, best.latency
+        );
+        Ok(best)
+    }
+
+    /// Sync now and then every `period` in a background task.
+    pub fn spawn(self, period: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.sync().await {
+                    error!("Time sync error: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn sample(&self) -> Result<ClockSample> {
+        let sent = millis();
+        let time = self.client.server_time().await?;
+        let received = millis();
+        let server = time.millis().ok_or_else(|| {
+            BybitError::InvalidResponse(format!("server time {}", time.time_nano))
+        })?;
+        Ok(ClockSample::estimate(sent, server, received))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        let sample = ClockSample::estimate(1_000, 1_550, 1_100);
+        assert_eq!(sample.latency, 100);
+        assert_eq!(sample.offset, 500);
+
+        let sample = ClockSample::estimate(1_000, 900, 1_040);
+        assert_eq!(sample.latency, 40);
+        assert_eq!(sample.offset, -120);
+    }
+
+    #[test]
+    fn test_clock() {
+        let clock = Clock::default();
+        clock.clone().set_offset(-60_000);
+        assert_eq!(clock.offset(), -60_000);
+        assert!(clock.timestamp() + 60_000 <= millis());
+    }
+}
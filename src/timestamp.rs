@@ -0,0 +1,177 @@
+//! Timestamps of responses.
+//!
+//! Bybit sends timestamps (ms) either as numbers, e.g. `ts`, or as strings, e.g. `createdTime`.
+//! Both are deserialized into [`Timestamp`]: milliseconds as `u64` by default, or
+//! `chrono::DateTime<Utc>` with the `chrono` feature.
+
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+
+/// A timestamp with millisecond precision.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = u64;
+/// A timestamp with millisecond precision.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// Convert a timestamp back to milliseconds since the epoch.
+pub trait ToMillis {
+    fn to_millis(&self) -> u64;
+}
+
+impl ToMillis for u64 {
+    fn to_millis(&self) -> u64 {
+        *self
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToMillis for chrono::DateTime<chrono::Utc> {
+    fn to_millis(&self) -> u64 {
+        self.timestamp_millis() as u64
+    }
+}
+
+/// Create a timestamp from milliseconds since the epoch.
+/// `None` if it is out of the range of `chrono::DateTime`.
+pub fn from_millis(millis: u64) -> Option<Timestamp> {
+    #[cfg(not(feature = "chrono"))]
+    return Some(millis);
+    #[cfg(feature = "chrono")]
+    return chrono::DateTime::from_timestamp_millis(millis as i64);
+}
+
+struct TimestampVisitor;
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = Option<Timestamp>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a timestamp (ms) as a number or a string")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        from_millis(v)
+            .map(Some)
+            .ok_or_else(|| E::custom(format!("timestamp out of range: {}", v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        let v = u64::try_from(v).map_err(|_| E::custom(format!("negative timestamp: {}", v)))?;
+        self.visit_u64(v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.is_empty() {
+            return Ok(None);
+        }
+        let v = v
+            .parse()
+            .map_err(|_| E::custom(format!("invalid timestamp: {}", v)))?;
+        self.visit_u64(v)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+        d.deserialize_any(self)
+    }
+}
+
+/// Deserialize a timestamp from a number or a numeric string.
+fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+    deserializer
+        .deserialize_any(TimestampVisitor)?
+        .ok_or_else(|| de::Error::custom("empty timestamp"))
+}
+
+/// Deserialize an empty string, null or a missing field as `None`, otherwise a timestamp.
+fn opt<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Timestamp>, D::Error> {
+    deserializer.deserialize_any(TimestampVisitor)
+}
+
+/// (De)serialize a timestamp sent as a number, e.g. `ts`.
+pub(crate) mod number {
+    use super::{Timestamp, ToMillis};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(ts.to_millis())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        super::de(deserializer)
+    }
+}
+
+/// (De)serialize a timestamp sent as a string, e.g. `createdTime`.
+pub(crate) mod string {
+    use super::{Timestamp, ToMillis};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(ts: &Timestamp, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&ts.to_millis())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        super::de(deserializer)
+    }
+}
+
+/// (De)serialize an optional timestamp sent as a string. `None` is serialized as an empty string.
+pub(crate) mod opt_string {
+    use super::{Timestamp, ToMillis};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        ts: &Option<Timestamp>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match ts {
+            Some(ts) => serializer.collect_str(&ts.to_millis()),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Timestamp>, D::Error> {
+        super::opt(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Fields {
+        #[serde(deserialize_with = "de")]
+        ts: Timestamp,
+        #[serde(deserialize_with = "de")]
+        created_time: Timestamp,
+        #[serde(default, deserialize_with = "opt")]
+        next_funding_time: Option<Timestamp>,
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let fields: Fields =
+            serde_json::from_str(r#"{"ts":1672364262474,"created_time":"1672364262444"}"#).unwrap();
+        assert_eq!(fields.ts.to_millis(), 1672364262474);
+        assert_eq!(fields.created_time.to_millis(), 1672364262444);
+        assert_eq!(fields.next_funding_time, None);
+
+        let fields: Result<Fields, _> =
+            serde_json::from_str(r#"{"ts":1,"created_time":"","next_funding_time":""}"#);
+        assert!(fields.is_err());
+    }
+}
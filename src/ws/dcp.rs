@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use super::response::{BasePrivateResponse, Dcp, PrivatePongResponse};
+
+/// The product a DCP (disconnect-cancel-all) time window applies to.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DcpProduct {
+    Options,
+    Derivatives,
+    Spot,
+}
+
+/// A DCP status change reported by [`DcpSupervisor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DcpEvent {
+    /// The server reported a new DCP status of `product`.
+    Status {
+        /// Product. `OPTIONS`, `DERIVATIVES`, `SPOT`.
+        product: String,
+        /// Whether DCP is on.
+        enabled: bool,
+        /// The time window (s).
+        time_window: u32,
+    },
+    /// No pong was received within the time window. The server may have
+    /// cancelled all open orders.
+    Lapsed,
+    /// A pong was received again after a lapse.
+    Restored,
+    /// The private connection is closed. The server will cancel all open
+    /// orders once the time window expires.
+    Disconnected,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DcpMessage<'a> {
+    #[serde(borrow)]
+    Dcp(BasePrivateResponse<'a, Vec<Dcp<'a>>>),
+    Pong(PrivatePongResponse<'a>),
+}
+
+/// A client-side supervisor of DCP tied to the ping loop of a private channel.
+///
+/// The ping interval is shortened so that at least two heartbeats fit in the
+/// time window, and every status change is reported to the callback.
+pub struct DcpSupervisor {
+    time_window: Duration,
+    last_pong: Instant,
+    lapsed: bool,
+    status: HashMap<String, (bool, u32)>,
+    on_event: Box<dyn FnMut(DcpEvent) + Send>,
+}
+
+impl DcpSupervisor {
+    /// Create a supervisor of a DCP time window (s), which should match the one
+    /// set with `RestApiClient::set_dcp_window`.
+    pub fn new<F>(time_window: u32, on_event: F) -> Self
+    where
+        F: FnMut(DcpEvent) + Send + 'static,
+    {
+        Self {
+            time_window: Duration::from_secs(time_window.into()),
+            last_pong: Instant::now(),
+            lapsed: false,
+            status: HashMap::new(),
+            on_event: Box::new(on_event),
+        }
+    }
+
+    /// The ping interval of the private channel. Default interval is 20s.
+    pub(super) fn ping_interval(&self) -> Duration {
+        (self.time_window / 2).clamp(Duration::from_secs(1), Duration::from_secs(20))
+    }
+
+    /// Inspect a received message for `dcp` topic data and pongs.
+    pub(super) fn on_message(&mut self, content: &str) {
+        if !content.contains("\"dcp") && !content.contains("\"pong\"") {
+            return;
+        }
+        match serde_json::from_str(content) {
+            Ok(DcpMessage::Dcp(res)) => {
+                for dcp in res.data {
+                    self.on_status(dcp);
+                }
+            }
+            Ok(DcpMessage::Pong(pong)) if pong.op == "pong" => {
+                self.last_pong = Instant::now();
+                if self.lapsed {
+                    self.lapsed = false;
+                    (self.on_event)(DcpEvent::Restored);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => error!("Error: {}", e),
+        }
+    }
+
+    /// Check whether the heartbeat lapsed.
+    pub(super) fn check(&mut self) {
+        if !self.lapsed && self.last_pong.elapsed() > self.time_window {
+            self.lapsed = true;
+            (self.on_event)(DcpEvent::Lapsed);
+        }
+    }
+
+    /// Start the heartbeat afresh on a new connection.
+    pub(super) fn on_connected(&mut self) {
+        self.last_pong = Instant::now();
+        self.lapsed = false;
+    }
+
+    pub(super) fn on_disconnected(&mut self) {
+        (self.on_event)(DcpEvent::Disconnected);
+    }
+
+    fn on_status(&mut self, dcp: Dcp) {
+        let status = (dcp.dcp_status == "ON", dcp.time_window);
+        if self.status.get(dcp.product) == Some(&status) {
+            return;
+        }
+        self.status.insert(dcp.product.to_owned(), status);
+        (self.on_event)(DcpEvent::Status {
+            product: dcp.product.to_owned(),
+            enabled: status.0,
+            time_window: status.1,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_supervisor() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut supervisor = DcpSupervisor::new(10, move |e| sink.lock().unwrap().push(e));
+        assert_eq!(supervisor.ping_interval(), Duration::from_secs(5));
+
+        let content = r#"{"topic":"dcp.future","creationTime":1740000000000,"data":[{"product":"DERIVATIVES","dcpStatus":"ON","timeWindow":10}]}"#;
+        supervisor.on_message(content);
+        supervisor.on_message(content);
+        supervisor.last_pong -= Duration::from_secs(11);
+        supervisor.check();
+        supervisor.check();
+        supervisor.on_message(r#"{"req_id":"1","op":"pong","args":["1740000000000"],"conn_id":"cfcb4ocsvfriu23r3er0-1b"}"#);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                DcpEvent::Status {
+                    product: "DERIVATIVES".to_owned(),
+                    enabled: true,
+                    time_window: 10,
+                },
+                DcpEvent::Lapsed,
+                DcpEvent::Restored,
+            ]
+        );
+
+        // A reconnection after a long outage does not lapse right away.
+        supervisor.on_disconnected();
+        supervisor.last_pong -= Duration::from_secs(60);
+        supervisor.on_connected();
+        supervisor.check();
+        assert_eq!(events.lock().unwrap().last(), Some(&DcpEvent::Disconnected));
+    }
+}
@@ -1,21 +1,17 @@
-use super::callback::Callback;
 use super::response::FuturePublicResponseArg;
 use super::run;
-use super::HandlerFuture;
-use super::Subscriber;
+use super::Callback;
+use super::{Resubscriber, Subscriber};
 use crate::error::Result;
-use crate::{FutureRole, KlineInterval};
-
-const MAINNET_LINEAR: &str = "wss://stream.bybit.com/v5/public/linear";
-const MAINNET_INVERSE: &str = "wss://stream.bybit.com/v5/public/inverse";
-const TESTNET_LINEAR: &str = "wss://stream-testnet.bybit.com/v5/public/linear";
-const TESTNET_INVERSE: &str = "wss://stream-testnet.bybit.com/v5/public/inverse";
+use crate::{Environment, FutureRole, KlineInterval};
 
 pub enum OrderbookDepth {
     Level1,
     Level50,
     Level200,
+    /// Replaced by `Level1000` on Bybit.
     Level500,
+    Level1000,
 }
 
 impl From<OrderbookDepth> for u16 {
@@ -26,6 +22,7 @@ impl From<OrderbookDepth> for u16 {
             Level50 => 50,
             Level200 => 200,
             Level500 => 500,
+            Level1000 => 1000,
         }
     }
 }
@@ -40,6 +37,11 @@ impl FutureWebsocketApiClient {
         self.subscriber.sub_orderbook(symbol.as_ref(), depth.into());
     }
 
+    /// Subscribe to the 50 levels orderbook with RPI (retail price improvement) orders.
+    pub fn subscribe_rpi_orderbook<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_rpi_orderbook(symbol.as_ref());
+    }
+
     pub fn subscribe_trade<S: AsRef<str>>(&mut self, symbol: S) {
         self.subscriber.sub_trade(symbol.as_ref());
     }
@@ -52,11 +54,45 @@ impl FutureWebsocketApiClient {
         self.subscriber.sub_kline(symbol.as_ref(), interval.into());
     }
 
+    /// Subscribe to liquidations.
+    ///
+    /// Deprecated by Bybit, which pushes at most one liquidation per second.
+    /// Use `subscribe_all_liquidation` instead.
     pub fn subscribe_liquidation<S: AsRef<str>>(&mut self, symbol: S) {
         self.subscriber.sub_liquidation(symbol.as_ref());
     }
-    pub async fn run<'a>(&self, callback: Box<dyn FnMut(FuturePublicResponseArg) -> HandlerFuture + 'a + Send>) -> Result<()> {
-        run(&self.uri, self.subscriber.topics(), None, callback).await
+
+    /// Subscribe to all liquidations of the symbol.
+    pub fn subscribe_all_liquidation<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_all_liquidation(symbol.as_ref());
+    }
+
+    /// Subscribe to insurance pool balance changes.
+    ///
+    /// Note that insurance uses the coin, e.g., USDT, USDC, or `inverse` for inverse contracts.
+    pub fn subscribe_insurance<S: AsRef<str>>(&mut self, coin: S) {
+        self.subscriber.sub_insurance(coin.as_ref());
+    }
+
+    /// Subscribe to the order price limits.
+    pub fn subscribe_price_limit<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_price_limit(symbol.as_ref());
+    }
+
+    /// Subscribe to ADL alerts and insurance pool information.
+    ///
+    /// Note that ADL alert uses the coin, e.g., USDT, USDC, or `inverse` for inverse contracts.
+    pub fn subscribe_adl_alert<S: AsRef<str>>(&mut self, coin: S) {
+        self.subscriber.sub_adl_alert(coin.as_ref());
+    }
+
+    /// A handle to resubscribe topics, e.g. the orderbook after a gap, while the client is running.
+    pub fn resubscriber(&self) -> Resubscriber {
+        self.subscriber.resubscriber()
+    }
+
+    pub async fn run<'a>(&self, callback: Callback<'a, FuturePublicResponseArg>) -> Result<()> {
+        run::<FuturePublicResponseArg>(&self.uri, &self.subscriber, None, callback).await
     }
 }
 
@@ -68,19 +104,18 @@ pub struct FutureWebSocketApiClientBuilder {
 impl FutureWebSocketApiClientBuilder {
     /// Create a new `FutureWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
     pub fn new(role: FutureRole) -> Self {
-        let uri = match role {
-            FutureRole::Linear => MAINNET_LINEAR.to_string(),
-            FutureRole::Inverse => MAINNET_INVERSE.to_string(),
-        };
+        let uri = Environment::Mainnet.public_ws((&role).into());
         Self { uri, role }
     }
 
     /// Change channel URI to the testnet.
-    pub fn testnet(mut self) -> Self {
-        self.uri = match self.role {
-            FutureRole::Linear => TESTNET_LINEAR.to_string(),
-            FutureRole::Inverse => TESTNET_INVERSE.to_string(),
-        };
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the linear / inverse channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.public_ws((&self.role).into());
         self
     }
 
@@ -1,33 +1,41 @@
 mod callback;
+pub mod dcp;
 pub mod future;
 pub mod option;
 pub mod private;
 pub mod response;
 pub mod spot;
+pub mod spread;
+pub mod trade;
 pub mod websockets;
 
-use callback::Arg;
-use callback::Callback;
+pub use callback::Arg;
 use log::*;
-use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::net::TcpStream;
 use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
 use std::{sync::mpsc, thread, time::Duration};
 use tungstenite::{connect, stream::MaybeTlsStream, Message, WebSocket};
 
-use futures::future::BoxFuture;
+pub use crate::credentials::Credentials;
 use crate::error::Result;
-use crate::util::millis;
-use crate::util::sign;
-use crate::FutureRole;
+use crate::time_sync::Clock;
+use crate::{Category, FutureRole};
+use futures::future::BoxFuture;
 
+use self::dcp::DcpSupervisor;
 use self::future::FutureWebSocketApiClientBuilder;
 use self::option::OptionWebSocketApiClientBuilder;
 use self::private::PrivateWebSocketApiClientBuilder;
 use self::spot::SpotWebSocketApiClientBuilder;
+use self::spread::SpreadWebSocketApiClientBuilder;
+use self::trade::TradeWebSocketApiClientBuilder;
 type HandlerFuture = BoxFuture<'static, Result<()>>;
-/// A factory to create different kind of websocket api clients (spot / future / option / private).
+/// The callback of websocket api clients. It is called with every response received.
+pub type Callback<'a, A> =
+    Box<dyn for<'b> FnMut(<A as Arg>::ValueType<'b>) -> HandlerFuture + 'a + Send>;
+/// A factory to create different kind of websocket api clients (spot / future / option / spread / private / trade).
 pub struct WebSocketApiClient;
 
 impl WebSocketApiClient {
@@ -51,29 +59,51 @@ impl WebSocketApiClient {
         OptionWebSocketApiClientBuilder::new()
     }
 
+    /// Get a builder for building spread trading websocket api client.
+    pub fn spread() -> SpreadWebSocketApiClientBuilder {
+        SpreadWebSocketApiClientBuilder::new()
+    }
+
     /// Get a builder for building private websocket api client.
     pub fn private() -> PrivateWebSocketApiClientBuilder {
         PrivateWebSocketApiClientBuilder::new()
     }
+
+    /// Get a builder for building trade (order entry) websocket api client.
+    pub fn trade() -> TradeWebSocketApiClientBuilder {
+        TradeWebSocketApiClientBuilder::new()
+    }
 }
 
 struct Subscriber {
     topics: Vec<String>,
+    resubscriber: Resubscriber,
 }
 
 impl Subscriber {
     fn new() -> Self {
-        Self { topics: Vec::new() }
+        Self {
+            topics: Vec::new(),
+            resubscriber: Resubscriber::default(),
+        }
     }
 
     fn topics(&self) -> &Vec<String> {
         &self.topics
     }
 
+    fn resubscriber(&self) -> Resubscriber {
+        self.resubscriber.clone()
+    }
+
     fn sub_orderbook(&mut self, symbol: &str, depth: u16) {
         self.sub(format!("orderbook.{depth}.{symbol}"));
     }
 
+    fn sub_rpi_orderbook(&mut self, symbol: &str) {
+        self.sub(format!("orderbook.rpi.{symbol}"));
+    }
+
     fn sub_ticker(&mut self, symbol: &str) {
         self.sub(format!("tickers.{symbol}"));
     }
@@ -90,6 +120,22 @@ impl Subscriber {
         self.sub(format!("liquidation.{symbol}"));
     }
 
+    fn sub_all_liquidation(&mut self, symbol: &str) {
+        self.sub(format!("allLiquidation.{symbol}"));
+    }
+
+    fn sub_insurance(&mut self, coin: &str) {
+        self.sub(format!("insurance.{coin}"));
+    }
+
+    fn sub_price_limit(&mut self, symbol: &str) {
+        self.sub(format!("priceLimit.{symbol}"));
+    }
+
+    fn sub_adl_alert(&mut self, coin: &str) {
+        self.sub(format!("adlAlert.{coin}"));
+    }
+
     fn sub_lt_kline(&mut self, symbol: &str, interval: &str) {
         self.sub(format!("kline_lt.{interval}.{symbol}"));
     }
@@ -114,6 +160,26 @@ impl Subscriber {
         self.sub("order".to_string());
     }
 
+    fn sub_category_position(&mut self, category: Category) {
+        self.sub(format!("position.{}", category.as_str()));
+    }
+
+    fn sub_category_execution(&mut self, category: Category) {
+        self.sub(format!("execution.{}", category.as_str()));
+    }
+
+    fn sub_category_order(&mut self, category: Category) {
+        self.sub(format!("order.{}", category.as_str()));
+    }
+
+    fn sub_fast_execution(&mut self) {
+        self.sub("execution.fast".to_string());
+    }
+
+    fn sub_category_fast_execution(&mut self, category: Category) {
+        self.sub(format!("execution.fast.{}", category.as_str()));
+    }
+
     fn sub_wallet(&mut self) {
         self.sub("wallet".to_string());
     }
@@ -122,123 +188,211 @@ impl Subscriber {
         self.sub("greeks".to_string());
     }
 
+    fn sub_spread_order(&mut self) {
+        self.sub("spread.order".to_string());
+    }
+
+    fn sub_spread_execution(&mut self) {
+        self.sub("spread.execution".to_string());
+    }
+
+    fn sub_dcp(&mut self) {
+        for topic in ["dcp.future", "dcp.spread", "dcp.spot"] {
+            self.sub(topic.to_string());
+        }
+    }
+
     fn sub(&mut self, topic: String) {
         self.topics.push(topic);
     }
 }
 
+/// A handle to resubscribe topics of a running websocket api client.
+///
+/// Resubscribing (unsubscribe + subscribe) the `orderbook` topic forces the server to push
+/// a fresh snapshot, e.g. after a gap in the update ID is detected. Only topics the client
+/// subscribed to before running can be resubscribed.
+#[derive(Debug, Clone, Default)]
+pub struct Resubscriber {
+    pending: Arc<Mutex<Vec<String>>>,
+}
+
+impl Resubscriber {
+    /// Resubscribe `topic` on the next iteration of the read loop.
+    pub fn resubscribe<S: Into<String>>(&self, topic: S) {
+        let topic = topic.into();
+        let mut pending = self.pending.lock().unwrap();
+        if !pending.contains(&topic) {
+            pending.push(topic);
+        }
+    }
+
+    /// Resubscribe the `orderbook.{depth}.{symbol}` topic.
+    pub fn resubscribe_orderbook<D: Into<u16>>(&self, symbol: &str, depth: D) {
+        self.resubscribe(format!("orderbook.{}.{symbol}", depth.into()));
+    }
+
+    pub(crate) fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
+
 #[derive(Serialize)]
 struct Op<'a> {
     op: &'a str,
     args: Vec<String>,
 }
 
-pub struct Credentials {
-    pub api_key: String,
-    pub secret: String,
+async fn run<'a, A: Arg>(
+    uri: &str,
+    subscriber: &Subscriber,
+    credentials: Option<(&Credentials, &Clock)>,
+    callback: Callback<'a, A>,
+) -> Result<()> {
+    run_supervised::<A>(uri, subscriber, credentials, callback, None).await
 }
 
+async fn run_supervised<'a, A: Arg>(
+    uri: &str,
+    subscriber: &Subscriber,
+    credentials: Option<(&Credentials, &Clock)>,
+    callback: Callback<'a, A>,
+    mut dcp: Option<&mut DcpSupervisor>,
+) -> Result<()> {
+    let res = run_inner::<A>(uri, subscriber, credentials, callback, dcp.as_deref_mut()).await;
+    if let Some(dcp) = dcp {
+        dcp.on_disconnected();
+    }
+    res
+}
 
-
-async fn run<'a, A>(
+async fn run_inner<'a, A: Arg>(
     uri: &str,
-    topics: &Vec<String>,
-    credentials: Option<&Credentials>,
-    mut callback:  Box<dyn FnMut(A) -> HandlerFuture + 'a + Send>,
-) -> Result<()>
-where
-    A: Arg + Send + Sync + 'a + DeserializeOwned 
-{
+    subscriber: &Subscriber,
+    credentials: Option<(&Credentials, &Clock)>,
+    mut callback: Callback<'a, A>,
+    mut dcp: Option<&mut DcpSupervisor>,
+) -> Result<()> {
     let (mut ws, _) = connect(uri)?;
+    if let Some(dcp) = dcp.as_deref_mut() {
+        dcp.on_connected();
+    }
+
+    let interval = dcp
+        .as_ref()
+        .map_or(Duration::from_secs(20), |dcp| dcp.ping_interval());
 
     // Set read timeout to the underlying TCP stream.
     //
     // Read and write are both in the main thread loop. A blocking read call
     // will starve writing that causes ping op message can't be sent on time.
     // Read timeout mitigate this situation.
-    set_read_timeout(&ws);
+    set_read_timeout(&ws, interval.min(Duration::from_secs(10)));
 
     // Authenticate
-    if let Some(credentials) = credentials {
-        let req = auth_req(credentials);
+    if let Some((credentials, clock)) = credentials {
+        let req = auth_req(credentials, clock)?;
         ws.write_message(Message::Text(req))?;
     }
 
     // Subscribe
-    ws.write_message(Message::Text(subscription(topics)))?;
+    ws.write_message(Message::Text(subscription(
+        "subscribe",
+        subscriber.topics(),
+    )))?;
 
-    let rx = ping();
+    let rx = ping(interval);
     loop {
         // Ping
         if let Ok(ping) = rx.try_recv() {
             ws.write_message(Message::Text(ping.into()))?
         }
 
+        if let Some(dcp) = dcp.as_deref_mut() {
+            dcp.check();
+        }
+
+        // Resubscribe
+        for topic in subscriber.resubscriber.take() {
+            if !subscriber.topics().contains(&topic) {
+                warn!("Resubscribing a topic not subscribed: {}", topic);
+                continue;
+            }
+            info!("Resubscribing: {}", topic);
+            let topics = [topic];
+            ws.write_message(Message::Text(subscription("unsubscribe", &topics)))?;
+            ws.write_message(Message::Text(subscription("subscribe", &topics)))?;
+        }
+
         match ws.read_message() {
-            Ok(msg) => match msg {
-                Message::Text(content) => {
+            Ok(msg) => {
+                if let Message::Text(content) = msg {
                     log::info!("Received: {}", content);
-                    match serde_json::from_str(&content) {
-                        Ok(res) => { callback(res).await; },
+                    if let Some(dcp) = dcp.as_deref_mut() {
+                        dcp.on_message(&content);
+                    }
+                    match serde_json::from_str::<A::ValueType<'_>>(&content) {
+                        Ok(res) => {
+                            if let Err(e) = callback(res).await {
+                                error!("Error: {}", e);
+                            }
+                        }
                         Err(e) => error!("Error: {}", e),
                     };
                 }
-                _ => {}
-            },
+            }
             Err(e) => match e {
-                tungstenite::Error::Io(ref ee) => {
-                    if ee.kind() != std::io::ErrorKind::WouldBlock
-                        && ee.kind() != std::io::ErrorKind::TimedOut
-                    {
-                        Err(e)?
-                    }
-                }
+                tungstenite::Error::Io(ref ee)
+                    if ee.kind() == std::io::ErrorKind::WouldBlock
+                        || ee.kind() == std::io::ErrorKind::TimedOut => {}
                 _ => Err(e)?,
             },
         }
     }
 }
 
-fn set_read_timeout(ws: &WebSocket<MaybeTlsStream<TcpStream>>) {
+fn set_read_timeout(ws: &WebSocket<MaybeTlsStream<TcpStream>>, timeout: Duration) {
     match ws.get_ref() {
         MaybeTlsStream::Plain(s) => {
-            s.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            s.set_read_timeout(Some(timeout)).unwrap();
         }
         MaybeTlsStream::NativeTls(t) => {
-            t.get_ref()
-                .set_read_timeout(Some(Duration::from_secs(10)))
-                .unwrap();
+            t.get_ref().set_read_timeout(Some(timeout)).unwrap();
         }
         _ => unreachable!(),
     };
 }
 
-fn auth_req(credentials: &Credentials) -> String {
-    let expires = millis() + 10000;
+fn auth_req(credentials: &Credentials, clock: &Clock) -> Result<String> {
+    let expires = clock.timestamp() + 10000;
     let val = format!("GET/realtime{}", expires);
-    let signature = sign(&credentials.secret, &val);
+    let signature = credentials.sign(&val)?;
     let auth_req = Op {
         op: "auth",
-        args: vec![credentials.api_key.clone(), expires.to_string(), signature],
+        args: vec![
+            credentials.api_key().to_owned(),
+            expires.to_string(),
+            signature,
+        ],
     };
-    serde_json::to_string(&auth_req).unwrap()
+    Ok(serde_json::to_string(&auth_req).unwrap())
 }
 
-fn subscription(topics: &Vec<String>) -> String {
+fn subscription(op: &str, topics: &[String]) -> String {
     let sub = Op {
-        op: "subscribe",
-        args: topics.clone(),
+        op,
+        args: topics.to_vec(),
     };
     serde_json::to_string(&sub).unwrap()
 }
 
-fn ping() -> Receiver<&'static str> {
+fn ping(interval: Duration) -> Receiver<&'static str> {
     let (tx, rx) = mpsc::channel();
     thread::spawn(move || loop {
-        if let Err(_) = tx.send("{\"op\":\"ping\"}") {
+        if tx.send("{\"op\":\"ping\"}").is_err() {
             break;
         };
-        thread::sleep(Duration::from_secs(20));
+        thread::sleep(interval);
     });
     rx
 }
@@ -1,12 +1,11 @@
-use super::callback::Callback;
 use super::response::OptionPublicResponseArg;
 use super::run;
-use super::HandlerFuture;
-use super::Subscriber;
+use super::Callback;
+use super::{Resubscriber, Subscriber};
 use crate::error::Result;
-
-const MAINNET_OPTION: &str = "wss://stream.bybit.com/v5/public/option";
-const TESTNET_OPTION: &str = "wss://stream-testnet.bybit.com/v5/public/option";
+use crate::option::{Expiry, OptionInstrument};
+use crate::{Category, Environment};
+use std::ops::RangeBounds;
 
 pub enum OrderbookDepth {
     Level25,
@@ -44,8 +43,55 @@ impl OptionWebsocketApiClient {
         self.subscriber.sub_ticker(symbol.as_ref());
     }
 
-    pub async fn run<'a>(&self, callback: Box<dyn FnMut(OptionPublicResponseArg) -> HandlerFuture + 'a + Send>) -> Result<()> {
-        run(&self.uri, self.subscriber.topics(), None, callback).await
+    /// Subscribe to tickers of the option `symbols` that match `filter`.
+    ///
+    /// `symbols` are usually all option symbols of a base coin, see `RestApiClient::option_symbols`.
+    /// Returns the subscribed instruments.
+    pub fn subscribe_tickers_by<I, S, F>(&mut self, symbols: I, filter: F) -> Vec<OptionInstrument>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: Fn(&OptionInstrument) -> bool,
+    {
+        let mut instruments = Vec::new();
+        for symbol in symbols {
+            let symbol = symbol.as_ref();
+            // Subscribe the symbol as given, formatting the parsed strike may change it.
+            let Ok(instrument) = symbol.parse::<OptionInstrument>() else {
+                continue;
+            };
+            if filter(&instrument) {
+                self.subscriber.sub_ticker(symbol);
+                instruments.push(instrument);
+            }
+        }
+        instruments
+    }
+
+    /// Subscribe to tickers of the option `symbols` of an expiry with strikes in `strikes`, both calls and puts.
+    pub fn subscribe_expiry_tickers<I, S, R>(
+        &mut self,
+        symbols: I,
+        expiry: Expiry,
+        strikes: R,
+    ) -> Vec<OptionInstrument>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        R: RangeBounds<f64>,
+    {
+        self.subscribe_tickers_by(symbols, |i| {
+            i.expiry == expiry && strikes.contains(&i.strike)
+        })
+    }
+
+    /// A handle to resubscribe topics, e.g. the orderbook after a gap, while the client is running.
+    pub fn resubscriber(&self) -> Resubscriber {
+        self.subscriber.resubscriber()
+    }
+
+    pub async fn run<'a>(&self, callback: Callback<'a, OptionPublicResponseArg>) -> Result<()> {
+        run::<OptionPublicResponseArg>(&self.uri, &self.subscriber, None, callback).await
     }
 }
 
@@ -53,17 +99,28 @@ pub struct OptionWebSocketApiClientBuilder {
     uri: String,
 }
 
+impl Default for OptionWebSocketApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OptionWebSocketApiClientBuilder {
     /// Create a new `OptionWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
     pub fn new() -> Self {
         Self {
-            uri: MAINNET_OPTION.to_string(),
+            uri: Environment::Mainnet.public_ws(Category::Option),
         }
     }
 
     /// Change channel URI to the testnet.
-    pub fn testnet(mut self) -> Self {
-        self.uri = TESTNET_OPTION.to_string();
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the option channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.public_ws(Category::Option);
         self
     }
 
@@ -84,3 +141,20 @@ impl OptionWebSocketApiClientBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_tickers_by() {
+        let mut client = OptionWebSocketApiClientBuilder::new().build();
+        let symbols = ["BTC-29DEC23-3250.50-C", "BTC-29DEC23-4000-P", "BTCUSDT"];
+        let instruments = client.subscribe_tickers_by(symbols, |i| i.strike < 4000.0);
+        assert_eq!(instruments.len(), 1);
+        assert_eq!(
+            client.subscriber.topics(),
+            &vec!["tickers.BTC-29DEC23-3250.50-C".to_string()]
+        );
+    }
+}
@@ -1,16 +1,16 @@
-use super::callback::Callback;
+use super::dcp::DcpSupervisor;
 use super::response::PrivateResponseArg;
-use super::{HandlerFuture, Subscriber};
-use super::{run, Credentials};
-use crate::error::Result;
-
-const MAINNET_PRIVATE: &str = "wss://stream.bybit.com/v5/private";
-const TESTNET_PRIVATE: &str = "wss://stream-testnet.bybit.com/v5/private";
+use super::{run, run_supervised, Credentials};
+use super::{Callback, Subscriber};
+use crate::error::{BybitError, Result};
+use crate::time_sync::Clock;
+use crate::{Category, Environment};
 
 pub struct PrivateWebsocketApiClient {
     uri: String,
     subscriber: Subscriber,
     credentials: Credentials,
+    clock: Clock,
 }
 
 impl PrivateWebsocketApiClient {
@@ -33,31 +33,122 @@ impl PrivateWebsocketApiClient {
     pub fn subscribe_greek(&mut self) {
         self.subscriber.sub_greek();
     }
-    pub async fn run<'a>(&self, callback: Box<dyn FnMut(PrivateResponseArg) -> HandlerFuture + 'a + Send>) -> Result<()> {
-        run(
+
+    /// Subscribe to spread trading orders.
+    pub fn subscribe_spread_order(&mut self) {
+        self.subscriber.sub_spread_order();
+    }
+
+    /// Subscribe to spread trading executions.
+    pub fn subscribe_spread_execution(&mut self) {
+        self.subscriber.sub_spread_execution();
+    }
+
+    /// Subscribe to positions of `category` only. Fail with `InvalidCategory` for spot,
+    /// which has no positions.
+    pub fn subscribe_category_position(&mut self, category: Category) -> Result<()> {
+        match category {
+            Category::Linear | Category::Inverse | Category::Option => {
+                self.subscriber.sub_category_position(category);
+                Ok(())
+            }
+            _ => Err(BybitError::InvalidCategory(category.as_str().to_owned())),
+        }
+    }
+
+    /// Subscribe to orders of `category` only.
+    pub fn subscribe_category_order(&mut self, category: Category) {
+        self.subscriber.sub_category_order(category);
+    }
+
+    /// Subscribe to executions of `category` only.
+    pub fn subscribe_category_execution(&mut self, category: Category) {
+        self.subscriber.sub_category_execution(category);
+    }
+
+    /// Subscribe to the low latency execution stream. Its data carries fewer fields than `execution`.
+    pub fn subscribe_fast_execution(&mut self) {
+        self.subscriber.sub_fast_execution();
+    }
+
+    /// Subscribe to the low latency execution stream of `category` only. Fail with
+    /// `InvalidCategory` for option, which has no such stream.
+    pub fn subscribe_category_fast_execution(&mut self, category: Category) -> Result<()> {
+        match category {
+            Category::Linear | Category::Inverse | Category::Spot => {
+                self.subscriber.sub_category_fast_execution(category);
+                Ok(())
+            }
+            _ => Err(BybitError::InvalidCategory(category.as_str().to_owned())),
+        }
+    }
+
+    /// Subscribe to DCP (disconnect-cancel-all) status of all products.
+    pub fn subscribe_dcp(&mut self) {
+        self.subscriber.sub_dcp();
+    }
+
+    pub async fn run<'a>(&self, callback: Callback<'a, PrivateResponseArg>) -> Result<()> {
+        run::<PrivateResponseArg>(
             &self.uri,
-            self.subscriber.topics(),
-            Some(&self.credentials),
+            &self.subscriber,
+            Some((&self.credentials, &self.clock)),
             callback,
-        ).await
+        )
+        .await
+    }
+
+    /// Run the client and report DCP status changes to `dcp`. The ping interval is
+    /// shortened to fit the DCP time window. Subscribe to `dcp` to receive the server status.
+    pub async fn run_with_dcp<'a>(
+        &self,
+        dcp: &mut DcpSupervisor,
+        callback: Callback<'a, PrivateResponseArg>,
+    ) -> Result<()> {
+        run_supervised::<PrivateResponseArg>(
+            &self.uri,
+            &self.subscriber,
+            Some((&self.credentials, &self.clock)),
+            callback,
+            Some(dcp),
+        )
+        .await
     }
 }
 
 pub struct PrivateWebSocketApiClientBuilder {
     uri: String,
+    clock: Clock,
+}
+
+impl Default for PrivateWebSocketApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PrivateWebSocketApiClientBuilder {
     /// Create a new `PrivateWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
     pub fn new() -> Self {
         Self {
-            uri: MAINNET_PRIVATE.to_string(),
+            uri: Environment::Mainnet.private_ws(),
+            clock: Clock::default(),
         }
     }
 
     /// Change channel URI to the testnet.
-    pub fn testnet(mut self) -> Self {
-        self.uri = TESTNET_PRIVATE.to_string();
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the demo trading.
+    pub fn demo(self) -> Self {
+        self.env(Environment::Demo)
+    }
+
+    /// Change channel URI to the private channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.private_ws();
         self
     }
 
@@ -70,19 +161,29 @@ impl PrivateWebSocketApiClientBuilder {
         self
     }
 
+    /// Sign the auth request with `clock`, e.g. the clock synchronised by
+    /// [`TimeSync`](crate::TimeSync).
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Build a private websocket api client with api key and secret key.
     pub fn build_with_credentials<S: AsRef<str>>(
         self,
         api_key: S,
         secret: S,
     ) -> PrivateWebsocketApiClient {
+        self.build_with(Credentials::hmac(api_key, secret))
+    }
+
+    /// Build a private websocket api client with HMAC or RSA credentials.
+    pub fn build_with(self, credentials: Credentials) -> PrivateWebsocketApiClient {
         PrivateWebsocketApiClient {
             uri: self.uri,
             subscriber: Subscriber::new(),
-            credentials: Credentials {
-                api_key: api_key.as_ref().to_owned(),
-                secret: secret.as_ref().to_owned(),
-            },
+            credentials,
+            clock: self.clock,
         }
     }
 }
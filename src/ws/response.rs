@@ -1,25 +1,32 @@
 use super::callback::Arg;
-use serde::Deserialize;
+use crate::enums::{
+    empty_as_none, CancelType, Category, ExecType, OrderStatus, OrderType, PositionStatus, Side,
+    StopOrderType, TimeInForce, TpslMode, TriggerBy,
+};
+use crate::num::{Num, NumOwned, OptNum, OptNumOwned};
+use crate::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
 
 /// The pong/subscription response.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OpResponse<'a> {
     pub success: bool,
     pub ret_msg: &'a str,
     pub conn_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub req_id: Option<&'a str>,
     pub op: &'a str,
 }
 
 /// The option pong response of public channels.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OptionPongResponse<'a> {
     pub args: [&'a str; 1],
     pub op: &'a str,
 }
 
 /// The data in option subscription response.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionSubscriptionData<'a> {
     #[serde(borrow)]
@@ -28,18 +35,19 @@ pub struct OptionSubscriptionData<'a> {
 }
 
 /// The option subscription response.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OptionSubscriptionResponse<'a> {
     pub success: bool,
     pub conn_id: &'a str,
     pub data: OptionSubscriptionData<'a>,
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub type_: &'a str,
 }
 
 /// The pong response of private channels.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PrivatePongResponse<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub req_id: Option<&'a str>,
     pub op: &'a str,
     pub args: [&'a str; 1],
@@ -47,81 +55,85 @@ pub struct PrivatePongResponse<'a> {
 }
 
 /// The base response which contains common fields of public channels.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BasePublicResponse<'a, Data> {
     /// Topic name.
     pub topic: &'a str,
-    /// Data type. `snapshot`, `delta`.
-    #[serde(alias = "type")]
+    /// Data type. `snapshot`, `delta`. Empty for topics without data type, e.g. `priceLimit`.
+    #[serde(rename = "type", default, skip_serializing_if = "str::is_empty")]
     pub type_: &'a str,
     /// The timestamp (ms) that the system generates the data.
-    pub ts: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub ts: Timestamp,
     /// The data vary on the topic.
     pub data: Data,
 }
 
-
 /// The base response which contains common fields of public channels.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BasePublicResponseStatic<DataStatic> {
     /// Topic name.
     pub topic: String,
     /// Data type. `snapshot`, `delta`.
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub type_: String,
     /// The timestamp (ms) that the system generates the data.
-    pub ts: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub ts: Timestamp,
     /// The data vary on the topic.
     pub data: DataStatic,
 }
 
 /// The base ticker response which contains common fields.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BaseTickerPublicResponse<'a, Data> {
     /// Topic name.
     pub topic: &'a str,
     /// Data type. `snapshot`, `delta`.
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     pub type_: &'a str,
     /// Cross sequence.
     pub cs: u64,
     /// The timestamp (ms) that the system generates the data.
-    pub ts: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub ts: Timestamp,
     /// The spot/future ticker data.
     pub data: Data,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct BaseOptionPublicResponse<'a, Data> {
     /// message ID
     pub id: &'a str,
     /// Topic name.
     pub topic: &'a str,
-    #[serde(alias = "type")]
+    #[serde(rename = "type")]
     /// Data type. `snapshot`.
     pub type_: &'a str,
     /// The timestamp (ms) that the system generates the data.
-    pub ts: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub ts: Timestamp,
     /// The data vary on the topic.
     pub data: Data,
 }
 
 /// The base response which contains common fields of private channels.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BasePrivateResponse<'a, Data> {
-    /// Message ID.
+    /// Message ID. Empty for `execution.fast`.
+    #[serde(default, skip_serializing_if = "str::is_empty")]
     pub id: &'a str,
     /// Topic name.
     pub topic: &'a str,
     /// Data created timestamp (ms).
-    pub creation_time: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub creation_time: Timestamp,
     /// The data vary on the topic.
     pub data: Data,
 }
 
-
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct BasePrivateResponseStatic<Data> {
     /// Message ID.
@@ -129,20 +141,33 @@ pub struct BasePrivateResponseStatic<Data> {
     /// Topic name.
     pub topic: String,
     /// Data created timestamp (ms).
-    pub creation_time: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub creation_time: Timestamp,
     /// The data vary on the topic.
     pub data: Data,
 }
-/// The (price, size) pair of orderbook.
-#[derive(Deserialize, Debug)]
-pub struct OrderbookItem<'a>(pub &'a str, pub &'a str);
+/// The (price, size) pair of orderbook, sent as an array.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderbookItem<'a> {
+    #[cfg_attr(not(feature = "rust_decimal"), serde(borrow))]
+    pub price: Num<'a>,
+    pub size: Num<'a>,
+    #[cfg(feature = "rust_decimal")]
+    #[serde(skip)]
+    _lifetime: std::marker::PhantomData<&'a ()>,
+}
 
+impl Serialize for OrderbookItem<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.price, &self.size).serialize(serializer)
+    }
+}
 
-#[derive(Deserialize, Debug)]
-pub struct OrderbookItemStatic(pub String, pub String);
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderbookItemStatic(pub NumOwned, pub NumOwned);
 
 /// The orderbook data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Orderbook<'a> {
     /// Symbol name.
     pub s: &'a str,
@@ -155,11 +180,11 @@ pub struct Orderbook<'a> {
     /// So please overwrite your local orderbook.
     pub u: u64,
     /// Cross sequence. Option does not have this field.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub seq: Option<u64>,
 }
 
-
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct OrderbookStatic {
     /// Symbol name.
     pub s: String,
@@ -172,24 +197,79 @@ pub struct OrderbookStatic {
     /// So please overwrite your local orderbook.
     pub u: u64,
     /// Cross sequence. Option does not have this field.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub seq: Option<u64>,
 }
 
+/// The (price, non-RPI size, RPI size) triple of RPI orderbook, sent as an array.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RpiOrderbookItem<'a> {
+    #[cfg_attr(not(feature = "rust_decimal"), serde(borrow))]
+    pub price: Num<'a>,
+    /// The size of non-RPI orders.
+    pub size: Num<'a>,
+    /// The size of RPI orders.
+    pub rpi_size: Num<'a>,
+    #[cfg(feature = "rust_decimal")]
+    #[serde(skip)]
+    _lifetime: std::marker::PhantomData<&'a ()>,
+}
+
+impl Serialize for RpiOrderbookItem<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.price, &self.size, &self.rpi_size).serialize(serializer)
+    }
+}
+
+/// The RPI (retail price improvement) orderbook data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RpiOrderbook<'a> {
+    /// Symbol name.
+    pub s: &'a str,
+    /// Bids. For `snapshot` stream, the element is sorted by price in descending order.
+    pub b: Vec<RpiOrderbookItem<'a>>,
+    /// Asks. For `snapshot` stream, the element is sorted by price in ascending order.
+    pub a: Vec<RpiOrderbookItem<'a>>,
+    /// Update ID. Is a sequence.
+    /// Occasionally, you'll receive "u"=1, which is a snapshot data due to the restart of the service.
+    /// So please overwrite your local orderbook.
+    pub u: u64,
+    /// Cross sequence.
+    pub seq: u64,
+}
+
+/// Accept only `orderbook.rpi` topics, so that empty deltas of either orderbook
+/// are not taken for the other.
+fn rpi_orderbook<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<BasePublicResponse<'a, RpiOrderbook<'a>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let res = BasePublicResponse::<RpiOrderbook>::deserialize(deserializer)?;
+    if !res.topic.starts_with("orderbook.rpi.") {
+        return Err(serde::de::Error::custom("not an orderbook.rpi topic"));
+    }
+    Ok(res)
+}
+
 /// The trade data.
 #[allow(non_snake_case)]
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Trade<'a> {
     /// The timestamp (ms) that the order is filled.
-    pub T: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub T: Timestamp,
     /// Symbol name.
     pub s: &'a str,
     /// Side. `Buy`, `Sell`.
     pub S: &'a str,
     /// Trade size.
-    pub v: &'a str,
+    pub v: Num<'a>,
     /// Trade price.
-    pub p: &'a str,
+    pub p: Num<'a>,
     /// Direction of price change. Unique field for future.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub L: Option<&'a str>,
     /// Trade ID.
     pub i: &'a str,
@@ -198,238 +278,512 @@ pub struct Trade<'a> {
 }
 
 /// The spot ticker data. (`snapshot` only)
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SpotTicker<'a> {
     /// Symbol name.
     pub symbol: &'a str,
     /// Last price.
-    pub last_price: &'a str,
+    pub last_price: Num<'a>,
     /// The highest price in the last 24 hours.
-    pub high_price_24h: &'a str,
+    pub high_price_24h: Num<'a>,
     /// The lowest price in the last 24 hours.
-    pub low_price_24h: &'a str,
+    pub low_price_24h: Num<'a>,
     /// Percentage change of market price relative to 24h.
-    pub prev_price_24h: &'a str,
+    pub prev_price_24h: Num<'a>,
     /// Volume for 24h.
-    pub volume_24h: &'a str,
+    pub volume_24h: Num<'a>,
     /// Turnover for 24h.
-    pub turnover_24h: &'a str,
+    pub turnover_24h: Num<'a>,
     /// Percentage change of market price relative to 24h.
-    pub price_24h_pcnt: &'a str,
+    pub price_24h_pcnt: Num<'a>,
     /// USD index price. It can be empty.
-    pub usd_index_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub usd_index_price: OptNum<'a>,
 }
 
 /// The option ticker data. (`snapshot` only)
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionTicker<'a> {
     /// Symbol name.
     pub symbol: &'a str,
     /// Best bid price.
-    pub bid_price: &'a str,
+    pub bid_price: Num<'a>,
     /// Best bid size.
-    pub bid_size: &'a str,
+    pub bid_size: Num<'a>,
     /// Best bid iv.
-    pub bid_iv: &'a str,
+    pub bid_iv: Num<'a>,
     /// Best ask price.
-    pub ask_price: &'a str,
+    pub ask_price: Num<'a>,
     /// Best ask size.
-    pub ask_size: &'a str,
+    pub ask_size: Num<'a>,
     /// Best ask iv.
-    pub ask_iv: &'a str,
+    pub ask_iv: Num<'a>,
     /// Last price.
-    pub last_price: &'a str,
+    pub last_price: Num<'a>,
     /// The highest price in the last 24 hours.
-    pub high_price_24h: &'a str,
+    pub high_price_24h: Num<'a>,
     /// The lowest price in the last 24 hours.
-    pub low_price_24h: &'a str,
+    pub low_price_24h: Num<'a>,
     /// Market price.
-    pub mark_price: &'a str,
+    pub mark_price: Num<'a>,
     /// Index price.
-    pub index_price: &'a str,
+    pub index_price: Num<'a>,
     /// Mark price iv.
-    pub mark_price_iv: &'a str,
+    pub mark_price_iv: Num<'a>,
     /// Underlying price.
-    pub underlying_price: &'a str,
+    pub underlying_price: Num<'a>,
     /// Open interest size.
-    pub open_interest: &'a str,
+    pub open_interest: Num<'a>,
     /// Turnover for 24h.
-    pub turnover_24h: &'a str,
+    pub turnover_24h: Num<'a>,
     /// Volume for 24h.
-    pub volume_24h: &'a str,
+    pub volume_24h: Num<'a>,
     /// Total volume.
-    pub total_volume: &'a str,
+    pub total_volume: Num<'a>,
     /// Total turnover.
-    pub total_turnover: &'a str,
+    pub total_turnover: Num<'a>,
     /// Delta.
-    pub delta: &'a str,
+    pub delta: Num<'a>,
     /// Gamma.
-    pub gamma: &'a str,
+    pub gamma: Num<'a>,
     /// Vega.
-    pub vega: &'a str,
+    pub vega: Num<'a>,
     /// Theta.
-    pub theta: &'a str,
+    pub theta: Num<'a>,
     /// Predicated delivery price. It has value when 30 min before delivery.
-    pub predicted_delivery_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub predicted_delivery_price: OptNum<'a>,
     /// The change in the last 24 hous.
-    pub change_24h: &'a str,
+    pub change_24h: Num<'a>,
 }
 
 /// The future ticker data.
 ///
 /// This data utilises the snapshot field and delta field. `None` means field value has not changed.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FutureTicker<'a> {
     /// Symbol name.
     pub symbol: &'a str,
     /// Tick direction.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tick_direction: Option<&'a str>,
     /// Percentage change of market price in the last 24 hours.
-    pub price_24h_pcnt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub price_24h_pcnt: Option<Num<'a>>,
     /// Last price.
-    pub last_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub last_price: Option<Num<'a>>,
     /// Market price 24 hours ago.
-    pub prev_price_24h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub prev_price_24h: Option<Num<'a>>,
     /// The highest price in the last 24 hours.
-    pub high_price_24h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub high_price_24h: Option<Num<'a>>,
     /// The lowest price in the last 24 hours.
-    pub low_price_24h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub low_price_24h: Option<Num<'a>>,
     /// Market price an hour ago.
-    pub prev_price_1h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub prev_price_1h: Option<Num<'a>>,
     /// Mark price.
-    pub mark_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub mark_price: Option<Num<'a>>,
     /// Index price.
-    pub index_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub index_price: Option<Num<'a>>,
     /// Open interest size.
-    pub open_interest: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub open_interest: Option<Num<'a>>,
     /// Open interest value.
-    pub open_interest_value: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub open_interest_value: Option<Num<'a>>,
     /// Turnover for 24h.
-    pub turnover_24h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub turnover_24h: Option<Num<'a>>,
     /// Volume for 24h.
-    pub volume_24h: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub volume_24h: Option<Num<'a>>,
     /// Next funding timestamp (ms).
-    pub next_funding_time: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, with = "crate::timestamp::opt_string")]
+    pub next_funding_time: Option<Timestamp>,
     /// Funding rate.
-    pub funding_rate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub funding_rate: Option<Num<'a>>,
     /// Best bid price.
-    pub bid1_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub bid1_price: Option<Num<'a>>,
     /// Best bid size.
-    pub bid1_size: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub bid1_size: Option<Num<'a>>,
     /// Best ask price.
-    pub ask1_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub ask1_price: Option<Num<'a>>,
     /// Best ask size.
-    pub ask1_size: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub ask1_size: Option<Num<'a>>,
     /// Delivery date time (UTC+0). Unique field for inverse futures.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delivery_time: Option<&'a str>,
     /// Delivery fee rate. Unique field for inverse futures.
-    pub basis_rate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub basis_rate: Option<Num<'a>>,
     /// Delivery fee rate. Unique field for inverse futures.
-    pub delivery_fee_rate: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub delivery_fee_rate: Option<Num<'a>>,
     /// Predicated delivery price. Unique field for inverse futures.
-    pub predicted_delivery_price: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub predicted_delivery_price: Option<Num<'a>>,
+}
+
+/// The owned future ticker data, e.g. the merged state of snapshot and delta messages.
+///
+/// `None` means the field has not been received yet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FutureTickerStatic {
+    /// Symbol name.
+    pub symbol: String,
+    /// Tick direction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_direction: Option<String>,
+    /// Percentage change of market price in the last 24 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub price_24h_pcnt: Option<NumOwned>,
+    /// Last price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub last_price: Option<NumOwned>,
+    /// Market price 24 hours ago.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub prev_price_24h: Option<NumOwned>,
+    /// The highest price in the last 24 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub high_price_24h: Option<NumOwned>,
+    /// The lowest price in the last 24 hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub low_price_24h: Option<NumOwned>,
+    /// Market price an hour ago.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub prev_price_1h: Option<NumOwned>,
+    /// Mark price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub mark_price: Option<NumOwned>,
+    /// Index price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub index_price: Option<NumOwned>,
+    /// Open interest size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub open_interest: Option<NumOwned>,
+    /// Open interest value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub open_interest_value: Option<NumOwned>,
+    /// Turnover for 24h.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub turnover_24h: Option<NumOwned>,
+    /// Volume for 24h.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub volume_24h: Option<NumOwned>,
+    /// Next funding timestamp (ms).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, with = "crate::timestamp::opt_string")]
+    pub next_funding_time: Option<Timestamp>,
+    /// Funding rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub funding_rate: Option<NumOwned>,
+    /// Best bid price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub bid1_price: Option<NumOwned>,
+    /// Best bid size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub bid1_size: Option<NumOwned>,
+    /// Best ask price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub ask1_price: Option<NumOwned>,
+    /// Best ask size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub ask1_size: Option<NumOwned>,
+    /// Delivery date time (UTC+0). Unique field for inverse futures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_time: Option<String>,
+    /// Delivery fee rate. Unique field for inverse futures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub basis_rate: Option<NumOwned>,
+    /// Delivery fee rate. Unique field for inverse futures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub delivery_fee_rate: Option<NumOwned>,
+    /// Predicated delivery price. Unique field for inverse futures.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub predicted_delivery_price: Option<NumOwned>,
+}
+
+/// The spread trade data.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpreadTrade<'a> {
+    /// The timestamp (ms) that the order is filled.
+    #[serde(with = "crate::timestamp::number")]
+    pub T: Timestamp,
+    /// Spread symbol name.
+    pub s: &'a str,
+    /// Side. `Buy`, `Sell`.
+    pub S: &'a str,
+    /// Trade size.
+    pub v: Num<'a>,
+    /// Trade price. It can be negative.
+    pub p: Num<'a>,
+    /// Trade ID.
+    pub i: &'a str,
+}
+
+/// The spread ticker data. (`snapshot` only)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadTicker<'a> {
+    /// Spread symbol name.
+    pub symbol: &'a str,
+    /// Best bid price. It can be empty.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub bid_price: OptNum<'a>,
+    /// Best bid size. It can be empty.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub bid_size: OptNum<'a>,
+    /// Best ask price. It can be empty.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub ask_price: OptNum<'a>,
+    /// Best ask size. It can be empty.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub ask_size: OptNum<'a>,
+    /// Last price.
+    pub last_price: Num<'a>,
+    /// The highest price in the last 24 hours.
+    pub high_price_24h: Num<'a>,
+    /// The lowest price in the last 24 hours.
+    pub low_price_24h: Num<'a>,
+    /// Market price 24 hours ago.
+    pub prev_price_24h: Num<'a>,
+    /// Volume for 24h.
+    pub volume_24h: Num<'a>,
 }
 
 /// The (leveraged token) kline data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Kline<'a> {
     /// The start timestamp (ms)
-    pub start: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub start: Timestamp,
     /// The end timestamp (ms). It is current timestamp if it does not reach to the end time of candle.
-    pub end: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub end: Timestamp,
     /// Kline interval.
     pub interval: &'a str,
     /// Open price.
-    pub open: &'a str,
+    pub open: Num<'a>,
     /// Close price.
-    pub close: &'a str,
+    pub close: Num<'a>,
     /// Highest price.
-    pub high: &'a str,
+    pub high: Num<'a>,
     /// Lowest price.
-    pub low: &'a str,
+    pub low: Num<'a>,
     /// Trade volume. Leveraged token does not have this field.
-    pub volume: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub volume: Option<Num<'a>>,
     /// Turnover. Leveraged token does not have this field.
-    pub turnover: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub turnover: Option<Num<'a>>,
     /// Weather the tick is ended or not.
     pub confirm: bool,
     /// The timestamp (ms) of the last matched order in the candle.
-    pub timestamp: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub timestamp: Timestamp,
 }
 
 /// The liquidation data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Liquidation<'a> {
     /// The updated timestamp (ms).
-    pub updated_time: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub updated_time: Timestamp,
     /// Symbol name.
     pub symbol: &'a str,
     /// Order side. `Buy`, `Sell`.
     pub side: &'a str,
     /// Executed size.
-    pub size: &'a str,
+    pub size: Num<'a>,
     /// Executed price.
-    pub price: &'a str,
+    pub price: Num<'a>,
+}
+
+/// The liquidation data of all liquidations.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AllLiquidation<'a> {
+    /// The updated timestamp (ms).
+    #[serde(with = "crate::timestamp::number")]
+    pub T: Timestamp,
+    /// Symbol name.
+    pub s: &'a str,
+    /// Position side. `Buy`, `Sell`. When you receive a `Buy` update, this means that a long position has been liquidated.
+    pub S: &'a str,
+    /// Executed size.
+    pub v: Num<'a>,
+    /// Bankruptcy price.
+    pub p: Num<'a>,
+}
+
+/// The insurance pool data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Insurance<'a> {
+    /// Coin name.
+    pub coin: &'a str,
+    /// Symbols sharing the insurance pool, separated by commas.
+    pub symbols: &'a str,
+    /// Balance of the insurance pool.
+    pub balance: Num<'a>,
+    /// The updated timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub update_time: Timestamp,
+}
+
+/// The order price limit data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PriceLimit<'a> {
+    /// Symbol name.
+    pub symbol: &'a str,
+    /// Highest price of buy orders.
+    #[serde(rename = "buyLmt")]
+    pub buy_lmt: Num<'a>,
+    /// Lowest price of sell orders.
+    #[serde(rename = "sellLmt")]
+    pub sell_lmt: Num<'a>,
+}
+
+/// The ADL (auto-deleveraging) alert data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AdlAlert<'a> {
+    /// Coin name.
+    pub c: &'a str,
+    /// Symbol name.
+    pub s: &'a str,
+    /// Balance of the insurance pool.
+    pub b: Num<'a>,
+    /// Deprecated. Max balance of the insurance pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub mb: Option<Num<'a>>,
+    /// PnL ratio of the insurance pool.
+    pub i_pr: Num<'a>,
+    /// Symbol PnL ratio.
+    pub pr: Num<'a>,
+    /// Trigger threshold of ADL by the PnL ratio.
+    pub adl_tt: Num<'a>,
+    /// Stop ratio of ADL by the PnL ratio.
+    pub adl_sr: Num<'a>,
 }
 
 // The leveraged token ticker data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LtTicker<'a> {
     /// Symbol name.
     pub symbol: &'a str,
     /// Market price change percentage in the past 24 hours.
-    pub price_24h_pcnt: &'a str,
+    pub price_24h_pcnt: Num<'a>,
     /// The last price.
-    pub last_price: &'a str,
+    pub last_price: Num<'a>,
     /// Market price 24 hours ago.
-    pub prev_price_24h: &'a str,
+    pub prev_price_24h: Num<'a>,
     /// Highest price in the past 24 hours.
-    pub high_price_24h: &'a str,
+    pub high_price_24h: Num<'a>,
     /// Lowest price in the past 24 hours.
-    pub low_price24h: &'a str,
+    pub low_price24h: Num<'a>,
 }
 
 /// The leveraged token nav data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LtNav<'a> {
     /// The generated timestamp of nav.
-    pub time: u64,
+    #[serde(with = "crate::timestamp::number")]
+    pub time: Timestamp,
     /// Symbol name.
     pub symbol: &'a str,
     /// Net asset value.
-    pub nav: &'a str,
+    pub nav: Num<'a>,
     /// Total position value = basket value * total circulation.
-    pub basket_position: &'a str,
+    pub basket_position: Num<'a>,
     /// Leverage.
-    pub leverage: &'a str,
+    pub leverage: Num<'a>,
     /// Basket loan.
-    pub basket_loan: &'a str,
+    pub basket_loan: Num<'a>,
     /// Circulation.
-    pub circulation: &'a str,
+    pub circulation: Num<'a>,
     /// Basket.
-    pub basket: &'a str,
+    pub basket: Num<'a>,
 }
 
 /// The position data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Position<'a> {
     /// Product type.
     /// - Unified account: does not have this field.
     /// - Normal account: `linear`, `inverse`.
-    pub category: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
     /// Symbol name.
     pub symbol: &'a str,
-    /// Position side: `Buy`, `Sell`.
-    pub side: &'a str,
+    /// Position side: `Buy`, `Sell`. Empty (`None`) for an empty position in one-way mode.
+    #[serde(with = "empty_as_none")]
+    pub side: Option<Side>,
     /// Position size.
-    pub size: &'a str,
+    pub size: Num<'a>,
     /// Used to identify positions in different position modes.
     /// - 0 one-way mode position.
     /// - 1 Buy side of hedge-mode position.
@@ -438,69 +792,84 @@ pub struct Position<'a> {
     /// Trade mode. 0: cross margin, 1: isolated margin. Always 0 under unified margin account.
     pub trade_mode: u8,
     /// Position value.
-    pub position_value: &'a str,
+    pub position_value: Num<'a>,
     /// Risk limit ID.
     /// _Note_: for portfolio margin mode, it returns 0, which the risk limit value is invalid.
     pub risk_id: u16,
     /// Risk limit value corresponding to riskId.
     /// _Note_: for portfolio margin mode, it returns "", which the risk limit value is invalid.
-    pub risk_limit_value: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub risk_limit_value: OptNum<'a>,
     /// Entry price.
-    pub entry_price: &'a str,
+    pub entry_price: Num<'a>,
     /// Mark price
-    pub mark_price: &'a str,
+    pub mark_price: Num<'a>,
     /// Leverage.
     /// _Note_: for portfolio margin mode, it returns "", which the leverage value is invalid.
-    pub leverage: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leverage: OptNum<'a>,
     /// Position margin. Unified account does not have this field.
-    pub position_balance: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "rust_decimal", serde(default, with = "crate::num::opt"))]
+    pub position_balance: Option<Num<'a>>,
     /// Whether to add margin automatically. 0: false, 1: true. Unified account does not have this field.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auto_add_margin: Option<u8>,
     /// Position maintenance margin.
     /// _Note_: for portfolio margin mode, it returns "".
-    #[serde(alias = "positionMM")]
-    pub position_mm: &'a str,
+    #[serde(rename = "positionMM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub position_mm: OptNum<'a>,
     /// Position initial margin.
     /// _Note_: for portfolio margin mode, it returns "".
-    #[serde(alias = "positionIM")]
-    pub position_im: &'a str,
+    #[serde(rename = "positionIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub position_im: OptNum<'a>,
     /// Est.liquidation price. "" for Unified trade(spot/linear/options).
-    pub liq_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub liq_price: OptNum<'a>,
     /// Est.bankruptcy price. "" for Unified trade(spot/linear/options).
-    pub bust_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub bust_price: OptNum<'a>,
     /// Tp/Sl mode: `Full`, `Partial`.
-    pub tpsl_mode: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub tpsl_mode: Option<TpslMode>,
     /// Take profit price.
-    pub take_profit: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub take_profit: OptNum<'a>,
     /// Stop loss price.
-    pub stop_loss: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub stop_loss: OptNum<'a>,
     /// Trailing stop.
-    pub trailing_stop: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub trailing_stop: OptNum<'a>,
     /// Unrealised profit and loss.
-    pub unrealised_pnl: &'a str,
+    pub unrealised_pnl: Num<'a>,
     /// Cumulative realised PnL.
-    pub cum_realised_pnl: &'a str,
+    pub cum_realised_pnl: Num<'a>,
     /// Position status.
     /// -`Normal`.
     /// - `Liq`: in the liquidation progress.
     /// - `Adl`: in the auto-deleverage progress.
-    pub position_status: &'a str,
+    pub position_status: PositionStatus,
     /// Position created timestamp (ms).
-    pub created_time: &'a str,
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
     /// Position data updated timestamp (ms).
-    pub updated_time: &'a str,
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
 }
 
 /// The execution data.
 ///
 /// You may have multiple executions for one order in a single message.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Execution<'a> {
     /// Product type.
     /// - Unified account: `spot`, `linear`, `option`.
     /// - Normal account: `linear`, `inverse`.
-    pub category: &'a str,
+    pub category: Category,
     /// Symbol name.
     pub symbol: &'a str,
     /// Whether to borrow. Valid for `spot` only.
@@ -512,57 +881,93 @@ pub struct Execution<'a> {
     /// User customized order ID.
     pub order_link_id: &'a str,
     /// Side. `Buy`, `Sell`.
-    pub side: &'a str,
+    pub side: Side,
     /// Order price.
-    pub order_price: &'a str,
+    pub order_price: Num<'a>,
     /// Order qty.
-    pub order_qty: &'a str,
+    pub order_qty: Num<'a>,
     /// The remaining qty not executed.
-    pub leaves_qty: &'a str,
+    pub leaves_qty: Num<'a>,
     /// Order type. `Market`, `Limit`.
-    pub order_type: &'a str,
+    pub order_type: OrderType,
     /// Stop order type. If the order is not stop order, any type is not returned.
-    pub stop_order_type: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub stop_order_type: Option<StopOrderType>,
     /// Executed trading fee.
-    pub exec_fee: &'a str,
+    pub exec_fee: Num<'a>,
     /// Execution ID.
     pub exec_id: &'a str,
     /// Execution price.
-    pub exec_price: &'a str,
+    pub exec_price: Num<'a>,
     /// Execution qty.
-    pub exec_qty: &'a str,
+    pub exec_qty: Num<'a>,
     /// Executed type.
-    pub exec_type: &'a str,
+    pub exec_type: ExecType,
     /// Executed order value.
-    pub exec_value: &'a str,
+    pub exec_value: Num<'a>,
     /// Executed timestamp (ms).
-    pub exec_time: &'a str,
+    #[serde(with = "crate::timestamp::string")]
+    pub exec_time: Timestamp,
     /// Is maker order. true: maker, false: taker.
     pub is_maker: bool,
     /// Trading fee rate.
-    pub fee_rate: &'a str,
+    pub fee_rate: Num<'a>,
     /// Implied volatility. Valid for option.
-    pub trade_iv: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub trade_iv: OptNum<'a>,
     /// Implied volatility of mark price. Valid for option.
-    pub mark_iv: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub mark_iv: OptNum<'a>,
     /// The mark price of the symbol when executing.
-    pub mark_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub mark_price: OptNum<'a>,
     /// The index price of the symbol when executing.
-    pub index_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub index_price: OptNum<'a>,
     /// The underlying price of the symbol when executing. Valid for option.
-    pub underlying_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub underlying_price: OptNum<'a>,
     /// Paradigm block trade ID.
     pub block_trade_id: &'a str,
 }
 
+/// The fast execution data. It only carries the key fields of an execution.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FastExecution<'a> {
+    /// Product type. `spot`, `linear`, `inverse`, `option`.
+    pub category: Category,
+    /// Symbol name.
+    pub symbol: &'a str,
+    /// Execution ID.
+    pub exec_id: &'a str,
+    /// Execution price.
+    pub exec_price: Num<'a>,
+    /// Execution qty.
+    pub exec_qty: Num<'a>,
+    /// Order ID.
+    pub order_id: &'a str,
+    /// Is maker order. true: maker, false: taker.
+    pub is_maker: bool,
+    /// User customized order ID.
+    pub order_link_id: &'a str,
+    /// Side. `Buy`, `Sell`.
+    pub side: Side,
+    /// Executed timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub exec_time: Timestamp,
+    /// Cross sequence.
+    pub seq: u64,
+}
+
 /// The order data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Order<'a> {
     /// Product type.
     /// - Unified account: `spot`, `linear`, `option`.
     /// - Normal account: `linear`, `inverse`.
-    pub category: &'a str,
+    pub category: Category,
     /// Order ID.
     pub order_id: &'a str,
     /// User customised order ID.
@@ -574,73 +979,86 @@ pub struct Order<'a> {
     /// Symbol name.
     pub symbol: &'a str,
     /// Order price.
-    pub price: &'a str,
+    pub price: Num<'a>,
     /// Order qty.
-    pub qty: &'a str,
+    pub qty: Num<'a>,
     /// Side. `Buy`, `Sell`.
-    pub side: &'a str,
+    pub side: Side,
     /// Position index. Used to identify positions in different position modes.
     pub position_idx: u8,
     /// Order status.
-    pub order_status: &'a str,
+    pub order_status: OrderStatus,
     /// Cancel type.
-    pub cancel_type: &'a str,
+    pub cancel_type: CancelType,
     /// Reject reason.
     pub reject_reason: &'a str,
     /// Average filled price. If unfilled, it is "".
-    pub avg_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub avg_price: OptNum<'a>,
     /// The remaining qty not executed.
-    pub leaves_qty: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_qty: OptNum<'a>,
     /// The remaining value not executed.
-    pub leaves_value: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_value: OptNum<'a>,
     /// Cumulative executed order qty.
-    pub cum_exec_qty: &'a str,
+    pub cum_exec_qty: Num<'a>,
     /// Cumulative executed order value.
-    pub cum_exec_value: &'a str,
+    pub cum_exec_value: Num<'a>,
     /// Cumulative executed trading fee.
-    pub cum_exec_fee: &'a str,
+    pub cum_exec_fee: Num<'a>,
     /// Time in force.
-    pub time_in_force: &'a str,
+    pub time_in_force: TimeInForce,
     /// Order type. `Market`, `Limit`.
-    pub order_type: &'a str,
+    pub order_type: OrderType,
     /// Stop order type.
-    pub stop_order_type: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub stop_order_type: Option<StopOrderType>,
     /// Implied volatility.
-    pub order_iv: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub order_iv: OptNum<'a>,
     /// Trigger price. If stopOrderType=TrailingStop, it is activate price. Otherwise, it is trigger price.
-    pub trigger_price: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub trigger_price: OptNum<'a>,
     /// Take profit price.
-    pub take_profit: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub take_profit: OptNum<'a>,
     /// Stop loss price.
-    pub stop_loss: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub stop_loss: OptNum<'a>,
     /// The price type to trigger take profit.
-    pub tp_trigger_by: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub tp_trigger_by: Option<TriggerBy>,
     /// The price type to trigger stop loss.
-    pub sl_trigger_by: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub sl_trigger_by: Option<TriggerBy>,
     /// Trigger direction. 1: rise, 2: fall.
     pub trigger_direction: u8,
     /// The price type of trigger price.
-    pub trigger_by: &'a str,
+    #[serde(with = "empty_as_none")]
+    pub trigger_by: Option<TriggerBy>,
     /// Last price when place the order. For linear only.
-    pub last_price_on_created: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub last_price_on_created: OptNum<'a>,
     /// Reduce only. `true` means reduce position size.
     pub reduce_only: bool,
     /// Close on trigger.
     pub close_on_trigger: bool,
     /// Order created timestamp (ms).
-    pub created_time: &'a str,
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
     /// Order updated timestamp (ms).
-    pub updated_time: &'a str,
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
 }
 
-
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderStatic {
     /// Product type.
     /// - Unified account: `spot`, `linear`, `option`.
     /// - Normal account: `linear`, `inverse`.
-    pub category: String,
+    pub category: Category,
     /// Order ID.
     pub order_id: String,
     /// User customised order ID.
@@ -652,101 +1070,228 @@ pub struct OrderStatic {
     /// Symbol name.
     pub symbol: String,
     /// Order price.
-    pub price: String,
+    pub price: NumOwned,
     /// Order qty.
-    pub qty: String,
+    pub qty: NumOwned,
     /// Side. `Buy`, `Sell`.
-    pub side: String,
+    pub side: Side,
     /// Position index. Used to identify positions in different position modes.
     pub position_idx: u8,
     /// Order status.
-    pub order_status: String,
+    pub order_status: OrderStatus,
     /// Cancel type.
-    pub cancel_type: String,
+    pub cancel_type: CancelType,
     /// Reject reason.
     pub reject_reason: String,
     /// Average filled price. If unfilled, it is "".
-    pub avg_price:String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub avg_price: OptNumOwned,
     /// The remaining qty not executed.
-    pub leaves_qty: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_qty: OptNumOwned,
     /// The remaining value not executed.
-    pub leaves_value: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_value: OptNumOwned,
     /// Cumulative executed order qty.
-    pub cum_exec_qty: String,
+    pub cum_exec_qty: NumOwned,
     /// Cumulative executed order value.
-    pub cum_exec_value: String,
+    pub cum_exec_value: NumOwned,
     /// Cumulative executed trading fee.
-    pub cum_exec_fee: String,
+    pub cum_exec_fee: NumOwned,
     /// Time in force.
-    pub time_in_force: String,
+    pub time_in_force: TimeInForce,
     /// Order type. `Market`, `Limit`.
-    pub order_type: String,
+    pub order_type: OrderType,
     /// Stop order type.
-    pub stop_order_type: String,
+    #[serde(with = "empty_as_none")]
+    pub stop_order_type: Option<StopOrderType>,
     /// Implied volatility.
-    pub order_iv: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub order_iv: OptNumOwned,
     /// Trigger price. If stopOrderType=TrailingStop, it is activate price. Otherwise, it is trigger price.
-    pub trigger_price: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub trigger_price: OptNumOwned,
     /// Take profit price.
-    pub take_profit: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub take_profit: OptNumOwned,
     /// Stop loss price.
-    pub stop_loss: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub stop_loss: OptNumOwned,
     /// The price type to trigger take profit.
-    pub tp_trigger_by: String,
+    #[serde(with = "empty_as_none")]
+    pub tp_trigger_by: Option<TriggerBy>,
     /// The price type to trigger stop loss.
-    pub sl_trigger_by: String,
+    #[serde(with = "empty_as_none")]
+    pub sl_trigger_by: Option<TriggerBy>,
     /// Trigger direction. 1: rise, 2: fall.
     pub trigger_direction: u8,
     /// The price type of trigger price.
-    pub trigger_by: String,
+    #[serde(with = "empty_as_none")]
+    pub trigger_by: Option<TriggerBy>,
     /// Last price when place the order. For linear only.
-    pub last_price_on_created: String,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub last_price_on_created: OptNumOwned,
     /// Reduce only. `true` means reduce position size.
     pub reduce_only: bool,
     /// Close on trigger.
     pub close_on_trigger: bool,
     /// Order created timestamp (ms).
-    pub created_time: String,
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
     /// Order updated timestamp (ms).
-    pub updated_time: String,
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
+}
+/// The spread order data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadOrder<'a> {
+    /// Product type. `combination`.
+    pub category: &'a str,
+    /// Spread symbol name.
+    pub symbol: &'a str,
+    /// Order ID.
+    pub order_id: &'a str,
+    /// User customised order ID.
+    pub order_link_id: &'a str,
+    /// Side. `Buy`, `Sell`.
+    pub side: &'a str,
+    /// Order status.
+    pub order_status: &'a str,
+    /// Cancel type.
+    pub cancel_type: &'a str,
+    /// Reject reason.
+    pub reject_reason: &'a str,
+    /// Time in force.
+    pub time_in_force: &'a str,
+    /// Order price.
+    pub price: Num<'a>,
+    /// Order qty.
+    pub qty: Num<'a>,
+    /// Average filled price. If unfilled, it is "".
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub avg_price: OptNum<'a>,
+    /// The remaining qty not executed.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_qty: OptNum<'a>,
+    /// The remaining value not executed.
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub leaves_value: OptNum<'a>,
+    /// Cumulative executed order qty.
+    pub cum_exec_qty: Num<'a>,
+    /// Cumulative executed order value.
+    pub cum_exec_value: Num<'a>,
+    /// Order type. `Market`, `Limit`.
+    pub order_type: &'a str,
+    /// Order created timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub created_time: Timestamp,
+    /// Order updated timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub updated_time: Timestamp,
+}
+
+/// The spread execution data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpreadExecution<'a> {
+    /// Product type. `combination` for the spread, `spot` / `linear` for the legs.
+    pub category: &'a str,
+    /// Symbol name.
+    pub symbol: &'a str,
+    /// Order ID.
+    pub order_id: &'a str,
+    /// User customised order ID.
+    pub order_link_id: &'a str,
+    /// Side. `Buy`, `Sell`.
+    pub side: &'a str,
+    /// Order price.
+    pub order_price: Num<'a>,
+    /// Order qty.
+    pub order_qty: Num<'a>,
+    /// The remaining qty not executed.
+    pub leaves_qty: Num<'a>,
+    /// Order type. `Market`, `Limit`.
+    pub order_type: &'a str,
+    /// Executed trading fee.
+    pub exec_fee: Num<'a>,
+    /// Spread execution ID. Legs carry the ID of their spread execution.
+    pub parent_exec_id: &'a str,
+    /// Execution ID.
+    pub exec_id: &'a str,
+    /// Execution price.
+    pub exec_price: Num<'a>,
+    /// Execution qty.
+    pub exec_qty: Num<'a>,
+    /// Executed type.
+    pub exec_type: &'a str,
+    /// Executed order value.
+    pub exec_value: Num<'a>,
+    /// Executed timestamp (ms).
+    #[serde(with = "crate::timestamp::string")]
+    pub exec_time: Timestamp,
+    /// Is maker order. true: maker, false: taker.
+    pub is_maker: bool,
+    /// Trading fee rate.
+    pub fee_rate: Num<'a>,
 }
+
+/// The DCP (disconnect-cancel-all) status data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Dcp<'a> {
+    /// Product. `OPTIONS`, `DERIVATIVES`, `SPOT`.
+    pub product: &'a str,
+    /// DCP status. `ON`, `OFF`.
+    pub dcp_status: &'a str,
+    /// The time window (s).
+    pub time_window: u32,
+}
+
 /// The wallet coin data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct WalletCoin<'a> {
     /// Coin name, such as BTC, ETH, USDT, USDC.
     pub coin: &'a str,
     /// Equity of current coin.
-    pub equity: &'a str,
+    pub equity: Num<'a>,
     /// USD value of current coin. If this coin cannot be collateral, then it is 0.
-    pub usd_value: &'a str,
+    pub usd_value: Num<'a>,
     /// Wallet balance of current coin.
-    pub wallet_balance: &'a str,
+    pub wallet_balance: Num<'a>,
     /// Borrow amount of current coin.
-    pub borrow_amount: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub borrow_amount: OptNum<'a>,
     /// Available amount to borrow of current coin.
-    pub available_to_borrow: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub available_to_borrow: OptNum<'a>,
     /// Available amount to withdraw of current coin.
-    pub available_to_withdraw: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub available_to_withdraw: OptNum<'a>,
     /// Accrued interest.
-    pub accrued_interest: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub accrued_interest: OptNum<'a>,
     /// Pre-occupied margin for order. For portfolio margin mode, it returns "".
-    #[serde(alias = "totalOrderIM")]
-    pub total_order_im: &'a str,
+    #[serde(rename = "totalOrderIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_order_im: OptNum<'a>,
     /// Sum of initial margin of all positions + Pre-occupied liquidation fee. For portfolio margin mode, it returns "".
-    #[serde(alias = "totalPositionIM")]
-    pub total_position_im: &'a str,
+    #[serde(rename = "totalPositionIM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_position_im: OptNum<'a>,
     /// Sum of maintenance margin for all positions. For portfolio margin mode, it returns "".
-    #[serde(alias = "totalPositionMM")]
-    pub total_position_mm: &'a str,
+    #[serde(rename = "totalPositionMM")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_position_mm: OptNum<'a>,
     /// Unrealised P&L.
-    pub unrealised_pnl: &'a str,
+    pub unrealised_pnl: Num<'a>,
     /// Cumulative Realised P&L.
-    pub cum_realised_pnl: &'a str,
+    pub cum_realised_pnl: Num<'a>,
 }
 
 /// The wallet data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Wallet<'a> {
     /// Account type.
@@ -755,57 +1300,68 @@ pub struct Wallet<'a> {
     pub account_type: &'a str,
     /// Initial Margin Rate: Account Total Initial Margin Base Coin / Account Margin Balance Base Coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    #[serde(alias = "accountIMRate")]
-    pub account_im_rate: &'a str,
+    #[serde(rename = "accountIMRate")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub account_im_rate: OptNum<'a>,
     /// Maintenance Margin Rate: Account Total Maintenance Margin Base Coin / Account Margin Balance Base Coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    #[serde(alias = "accountMMRate")]
-    pub account_mm_rate: &'a str,
+    #[serde(rename = "accountMMRate")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub account_mm_rate: OptNum<'a>,
     /// Equity of account converted to usd：Account Margin Balance Base Coin + Account Option Value Base Coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_equity: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_equity: OptNum<'a>,
     /// Wallet Balance of account converted to usd：∑ Asset Wallet Balance By USD value of each asset.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_wallet_balance: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_wallet_balance: OptNum<'a>,
     /// Margin Balance of account converted to usd：totalWalletBalance + totalPerpUPL.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_margin_balance: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_margin_balance: OptNum<'a>,
     /// Available Balance of account converted to usd：Regular mode：totalMarginBalance - totalInitialMargin.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_available_balance: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_available_balance: OptNum<'a>,
     /// Unrealised P&L of perpetuals of account converted to usd：∑ Each perp upl by base coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    #[serde(alias = "totalPerpUPL")]
-    pub total_perp_upl: &'a str,
+    #[serde(rename = "totalPerpUPL")]
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_perp_upl: OptNum<'a>,
     /// Initial Margin of account converted to usd：∑ Asset Total Initial Margin Base Coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_initial_margin: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_initial_margin: OptNum<'a>,
     /// Maintenance Margin of account converted to usd: ∑ Asset Total Maintenance Margin Base Coin.
     /// In non-unified mode, the field will be returned as an empty string.
-    pub total_maintenance_margin: &'a str,
+    #[cfg_attr(feature = "rust_decimal", serde(with = "crate::num::opt"))]
+    pub total_maintenance_margin: OptNum<'a>,
     /// Coin.
     pub coin: Vec<WalletCoin<'a>>,
 }
 
 /// The greeks data.
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Greek<'a> {
     /// Base coin.
     pub base_coin: &'a str,
     /// Delta value.
-    pub total_delta: &'a str,
+    pub total_delta: Num<'a>,
     /// Gamma value.
-    pub total_gamma: &'a str,
+    pub total_gamma: Num<'a>,
     /// Vega value.
-    pub total_vega: &'a str,
+    pub total_vega: Num<'a>,
     /// Theta value.
-    pub total_theta: &'a str,
+    pub total_theta: Num<'a>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum SpotPublicResponse<'a> {
+    #[serde(borrow, deserialize_with = "rpi_orderbook")]
+    RpiOrderbook(BasePublicResponse<'a, RpiOrderbook<'a>>),
     #[serde(borrow)]
     Orderbook(BasePublicResponse<'a, Orderbook<'a>>),
     Trade(BasePublicResponse<'a, Vec<Trade<'a>>>),
@@ -815,21 +1371,28 @@ pub enum SpotPublicResponse<'a> {
     LtNav(BasePublicResponse<'a, LtNav<'a>>),
     Op(OpResponse<'a>),
 }
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SpotPublicResponseArg;
 impl Arg for SpotPublicResponseArg {
     type ValueType<'a> = SpotPublicResponse<'a>;
 }
 
-#[derive(Deserialize, Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum FuturePublicResponse<'a> {
+    #[serde(borrow, deserialize_with = "rpi_orderbook")]
+    RpiOrderbook(BasePublicResponse<'a, RpiOrderbook<'a>>),
     #[serde(borrow)]
     Orderbook(BasePublicResponse<'a, Orderbook<'a>>),
     Trade(BasePublicResponse<'a, Vec<Trade<'a>>>),
     Ticker(BaseTickerPublicResponse<'a, FutureTicker<'a>>),
     Kline(BasePublicResponse<'a, Vec<Kline<'a>>>),
     Liquidation(BasePublicResponse<'a, Liquidation<'a>>),
+    AllLiquidation(BasePublicResponse<'a, Vec<AllLiquidation<'a>>>),
+    Insurance(BasePublicResponse<'a, Vec<Insurance<'a>>>),
+    PriceLimit(BasePublicResponse<'a, PriceLimit<'a>>),
+    AdlAlert(BasePublicResponse<'a, Vec<AdlAlert<'a>>>),
     Op(OpResponse<'a>),
 }
 #[derive(Deserialize)]
@@ -838,7 +1401,8 @@ impl Arg for FuturePublicResponseArg {
     type ValueType<'a> = FuturePublicResponse<'a>;
 }
 
-#[derive(Deserialize, Debug)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum OptionPublicResponse<'a> {
     #[serde(borrow)]
@@ -854,15 +1418,76 @@ impl Arg for OptionPublicResponseArg {
     type ValueType<'a> = OptionPublicResponse<'a>;
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum SpreadPublicResponse<'a> {
+    #[serde(borrow)]
+    Orderbook(BasePublicResponse<'a, Orderbook<'a>>),
+    Trade(BasePublicResponse<'a, Vec<SpreadTrade<'a>>>),
+    Ticker(BasePublicResponse<'a, SpreadTicker<'a>>),
+    Op(OpResponse<'a>),
+}
+#[derive(Deserialize)]
+pub struct SpreadPublicResponseArg;
+impl Arg for SpreadPublicResponseArg {
+    type ValueType<'a> = SpreadPublicResponse<'a>;
+}
+
+/// Define a deserializer of a private response that only accepts the topics matched
+/// by `$accept`. Order and execution data share most fields, so without it a message
+/// that fails to parse as its own type could be taken for another one.
+macro_rules! topic_filter {
+    ($name:ident, $data:ty, |$topic:ident| $accept:expr) => {
+        fn $name<'de: 'a, 'a, D>(
+            deserializer: D,
+        ) -> Result<BasePrivateResponse<'a, Vec<$data>>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let res = BasePrivateResponse::<Vec<$data>>::deserialize(deserializer)?;
+            let $topic = res.topic;
+            if !$accept {
+                return Err(serde::de::Error::custom(format!(
+                    "unexpected topic {}",
+                    $topic
+                )));
+            }
+            Ok(res)
+        }
+    };
+}
+
+topic_filter!(execution, Execution<'a>, |topic| {
+    topic == "execution"
+        || (topic.starts_with("execution.") && !topic.starts_with("execution.fast"))
+});
+topic_filter!(fast_execution, FastExecution<'a>, |topic| topic
+    .starts_with("execution.fast"));
+topic_filter!(spread_execution, SpreadExecution<'a>, |topic| topic
+    == "spread.execution");
+topic_filter!(order, Order<'a>, |topic| topic == "order"
+    || topic.starts_with("order."));
+topic_filter!(spread_order, SpreadOrder<'a>, |topic| topic
+    == "spread.order");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum PrivateResponse<'a> {
     #[serde(borrow)]
     Position(BasePrivateResponse<'a, Vec<Position<'a>>>),
+    #[serde(borrow, deserialize_with = "execution")]
     Execution(BasePrivateResponse<'a, Vec<Execution<'a>>>),
+    #[serde(borrow, deserialize_with = "spread_execution")]
+    SpreadExecution(BasePrivateResponse<'a, Vec<SpreadExecution<'a>>>),
+    #[serde(borrow, deserialize_with = "fast_execution")]
+    FastExecution(BasePrivateResponse<'a, Vec<FastExecution<'a>>>),
+    #[serde(borrow, deserialize_with = "order")]
     Order(BasePrivateResponse<'a, Vec<Order<'a>>>),
+    #[serde(borrow, deserialize_with = "spread_order")]
+    SpreadOrder(BasePrivateResponse<'a, Vec<SpreadOrder<'a>>>),
     Wallet(BasePrivateResponse<'a, Vec<Wallet<'a>>>),
     Greek(BasePrivateResponse<'a, Vec<Greek<'a>>>),
+    Dcp(BasePrivateResponse<'a, Vec<Dcp<'a>>>),
     Pong(PrivatePongResponse<'a>),
     Op(OpResponse<'a>),
 }
@@ -871,3 +1496,202 @@ pub struct PrivateResponseArg;
 impl Arg for PrivateResponseArg {
     type ValueType<'a> = PrivateResponse<'a>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const POSITION: &str = r#"{"id":"1003076014fb7eedb-c7e6-45d6-a8c1-270f0169171a","topic":"position","creationTime":1697682317044,"data":[{"positionIdx":0,"tradeMode":0,"riskId":1,"riskLimitValue":"2000000","symbol":"BTCUSDT","side":"","size":"0","entryPrice":"0","leverage":"10","positionValue":"0","positionBalance":"0","markPrice":"28184.5","positionIM":"0","positionMM":"0","takeProfit":"0","stopLoss":"0","trailingStop":"0","unrealisedPnl":"0","cumRealisedPnl":"-25.06579337","createdTime":"1694402496913","updatedTime":"1697682317038","tpslMode":"Full","liqPrice":"","bustPrice":"","category":"linear","positionStatus":"Normal","adlRankIndicator":0,"autoAddMargin":0,"leverageSysUpdatedTime":"","mmrSysUpdatedTime":"","seq":8327597863,"isReduceOnly":false}]}"#;
+
+    /// Serialize a response and parse it again. Return the serialized JSON.
+    macro_rules! round_trip {
+        ($ty:ty, $content:expr) => {{
+            let res: $ty = serde_json::from_str($content).unwrap();
+            let json = serde_json::to_string(&res).unwrap();
+            let again: $ty = serde_json::from_str(&json).unwrap();
+            assert_eq!(again, res);
+            serde_json::from_str::<Value>(&json).unwrap()
+        }};
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = r#"{"topic":"orderbook.50.BTCUSDT","type":"snapshot","ts":1672304484978,"data":{"s":"BTCUSDT","b":[["16493.50","0.006"]],"a":[["16611.00","0.029"]],"u":18521288,"seq":7961638724}}"#;
+        let json = round_trip!(FuturePublicResponse, content);
+        assert_eq!(json, serde_json::from_str::<Value>(content).unwrap());
+
+        let content = r#"{"topic":"tickers.BTCUSDT","type":"delta","data":{"symbol":"BTCUSDT","bid1Price":"17215.50","bid1Size":"84.489"},"cs":24987956059,"ts":1673272861686}"#;
+        let json = round_trip!(FuturePublicResponse, content);
+        assert_eq!(json, serde_json::from_str::<Value>(content).unwrap());
+
+        let json = round_trip!(PrivateResponse, POSITION);
+        let position = &json["data"][0];
+        assert_eq!(position["createdTime"], "1694402496913");
+        assert_eq!(position["positionMM"], "0");
+        assert_eq!(position["liqPrice"], "");
+        assert_eq!(position["side"], "");
+        assert_eq!(json["creationTime"], 1697682317044u64);
+    }
+
+    #[test]
+    fn test_spread() {
+        let content = r#"{"topic":"tickers.SOLUSDT_SOL/USDT","ts":1744168585009,"type":"snapshot","data":{"symbol":"SOLUSDT_SOL/USDT","bidPrice":"20.3359","bidSize":"1.7","askPrice":"","askSize":"","lastPrice":"21.8182","highPrice24h":"24.2356","lowPrice24h":"-3","prevPrice24h":"22.1468","volume24h":"23309.9"}}"#;
+        match serde_json::from_str(content).unwrap() {
+            SpreadPublicResponse::Ticker(res) => {
+                assert_eq!(res.data.low_price_24h.to_string(), "-3")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"spread.execution","id":"1448939_SOLUSDT_SOL/USDT_28731107101","creationTime":1744170337913,"data":[{"category":"combination","symbol":"SOLUSDT_SOL/USDT","isLeverage":"","orderId":"5e010c35-2b44-4f03-8081-8fa31fb73376","orderLinkId":"","side":"Buy","orderPrice":"21","orderQty":"2","leavesQty":"0","createType":"CreateByUser","orderType":"Limit","execFee":"0.005061","parentExecId":"","execId":"82c82077-0caa-5304-894d-58a50a342bd7","execPrice":"20.2","execQty":"2","execPnl":"","execType":"Trade","execValue":"40.4","execTime":"1744170337907","isMaker":false,"feeRate":"0.0001","markPrice":"","closedSize":"","seq":1688002}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::SpreadExecution(res) => {
+                assert_eq!(res.data[0].exec_qty.to_string(), "2")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"spread.order","id":"1448939_SOLUSDT_SOL/USDT_28731107101","creationTime":1744170337912,"data":[{"category":"combination","symbol":"SOLUSDT_SOL/USDT","parentOrderId":"","orderId":"5e010c35-2b44-4f03-8081-8fa31fb73376","orderLinkId":"","side":"Buy","orderStatus":"Filled","cancelType":"UNKNOWN","rejectReason":"EC_NoError","timeInForce":"GTC","price":"21","qty":"2","avgPrice":"20.2","leavesQty":"0","leavesValue":"0","cumExecQty":"2","cumExecValue":"40.4","cumExecFee":"","orderType":"Limit","isLeverage":"","createdTime":"1744170337907","updatedTime":"1744170337912","feeCurrency":"","createType":"CreateByUser","smpType":"None","smpGroup":0,"smpOrderId":""}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::SpreadOrder(res) => assert_eq!(res.data[0].order_status, "Filled"),
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let content = r#"{"id":"5923240c6880ab-c59f-420b-9adb-3639adc9dd90","topic":"order","creationTime":1672364262474,"data":[{"symbol":"ETH-30DEC22-1400-C","orderId":"5cf98598-39a7-459e-97bf-76ca765ee020","side":"Sell","orderType":"Market","cancelType":"UNKNOWN","price":"72.5","qty":"1","orderIv":"","timeInForce":"IOC","orderStatus":"Filled","orderLinkId":"","lastPriceOnCreated":"","reduceOnly":false,"leavesQty":"","leavesValue":"","cumExecQty":"1","cumExecValue":"75","avgPrice":"75","blockTradeId":"","positionIdx":0,"cumExecFee":"0.358635","createdTime":"1672364262444","updatedTime":"1672364262457","rejectReason":"EC_NoError","stopOrderType":"","tpslMode":"","triggerPrice":"","takeProfit":"","stopLoss":"","tpTriggerBy":"","slTriggerBy":"","tpLimitPrice":"","slLimitPrice":"","triggerDirection":0,"triggerBy":"","closeOnTrigger":false,"category":"option","placeType":"price","smpType":"None","smpGroup":0,"smpOrderId":"","isLeverage":""}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::Order(res) => {
+                let order = &res.data[0];
+                assert_eq!(order.category, Category::Option);
+                assert_eq!(order.side, Side::Sell);
+                assert_eq!(order.order_status, OrderStatus::Filled);
+                assert_eq!(order.time_in_force, TimeInForce::Ioc);
+                assert_eq!(order.cancel_type, CancelType::NotCancelled);
+                assert_eq!(order.stop_order_type, None);
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_position() {
+        let content = POSITION;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::Position(res) => {
+                let position = &res.data[0];
+                assert_eq!(position.side, None);
+                assert_eq!(position.tpsl_mode, Some(TpslMode::Full));
+                assert_eq!(position.cum_realised_pnl.to_string(), "-25.06579337");
+                #[cfg(not(feature = "rust_decimal"))]
+                assert_eq!(position.liq_price, "");
+                #[cfg(feature = "rust_decimal")]
+                assert_eq!(position.liq_price, None);
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_dcp() {
+        let content = r#"{"topic":"dcp.future","creationTime":1740000000000,"data":[{"product":"DERIVATIVES","dcpStatus":"ON","timeWindow":10}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::Dcp(res) => assert_eq!(res.data[0].time_window, 10),
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_fast_execution() {
+        let content = r#"{"topic":"execution.fast","creationTime":1716800399338,"data":[{"category":"linear","symbol":"ICPUSDT","execId":"3510f361-0add-5c7b-a2e7-9679810944fc","execPrice":"12.015","execQty":"3000","orderId":"443d63fa-b4c3-4297-b7b1-23bca88b04dc","isMaker":false,"orderLinkId":"test-00001","side":"Sell","execTime":"1716800399334","seq":34771365464}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            PrivateResponse::FastExecution(res) => {
+                assert_eq!(res.id, "");
+                assert_eq!(res.data[0].exec_qty.to_string(), "3000");
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_private_topic() {
+        // A malformed full execution must not be taken for a fast execution.
+        let content = r#"{"topic":"execution","id":"1","creationTime":1716800399338,"data":[{"category":"linear","symbol":"ICPUSDT","execId":"3510f361-0add-5c7b-a2e7-9679810944fc","execPrice":"12.015","execQty":"3000","orderId":"443d63fa-b4c3-4297-b7b1-23bca88b04dc","isMaker":false,"orderLinkId":"","side":"Sell","execTime":"1716800399334","seq":34771365464}]}"#;
+        assert!(serde_json::from_str::<PrivateResponse>(content).is_err());
+    }
+
+    #[test]
+    fn test_rpi_orderbook() {
+        let content = r#"{"topic":"orderbook.rpi.BTCUSDT","ts":1747638536054,"type":"snapshot","data":{"s":"BTCUSDT","b":[["103300.20","0.001","0.02"]],"a":[["103300.30","0.215","0"]],"u":1,"seq":72526155},"cts":1747638536049}"#;
+        match serde_json::from_str(content).unwrap() {
+            SpotPublicResponse::RpiOrderbook(res) => {
+                assert_eq!(res.data.b[0].rpi_size.to_string(), "0.02");
+                assert_eq!(res.data.a[0].size.to_string(), "0.215");
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967466,"data":{"s":"BTCUSDT","b":[["30247.20","30.028"]],"a":[],"u":177400507,"seq":66544703342},"cts":1687940967464}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::Orderbook(res) => {
+                assert_eq!(res.data.b[0].size.to_string(), "30.028")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"orderbook.rpi.BTCUSDT","type":"delta","ts":1747638536054,"data":{"s":"BTCUSDT","b":[],"a":[],"u":2,"seq":72526156},"cts":1747638536049}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::RpiOrderbook(res) => assert_eq!(res.data.u, 2),
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"orderbook.50.BTCUSDT","type":"delta","ts":1687940967466,"data":{"s":"BTCUSDT","b":[],"a":[],"u":177400508,"seq":66544703343},"cts":1687940967464}"#;
+        match serde_json::from_str(content).unwrap() {
+            SpotPublicResponse::Orderbook(res) => assert_eq!(res.data.u, 177400508),
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_future_public_response() {
+        let content = r#"{"topic":"allLiquidation.ROSEUSDT","type":"snapshot","ts":1739502303204,"data":[{"T":1739502302929,"s":"ROSEUSDT","S":"Sell","v":"20000","p":"0.04499"}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::AllLiquidation(res) => {
+                assert_eq!(res.data[0].p.to_string(), "0.04499")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"insurance.USDT","type":"delta","ts":1747722930000,"data":[{"coin":"USDT","symbols":"GRIFFAINUSDT","balance":"25193.66754136","updateTime":"1747722930000"}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::Insurance(res) => {
+                assert_eq!(res.data[0].balance.to_string(), "25193.66754136")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"priceLimit.BTCUSDT","ts":1730427365000,"data":{"symbol":"BTCUSDT","buyLmt":"71800.00","sellLmt":"70000.00"}}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::PriceLimit(res) => {
+                assert_eq!(res.data.buy_lmt.to_string(), "71800.00")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"adlAlert.USDT","type":"snapshot","ts":1747722930000,"data":[{"c":"USDT","s":"BTCUSDT","b":"12076.3","mb":"12076.3","i_pr":"-0.01","pr":"-0.02","adl_tt":"-0.3","adl_sr":"-0.25"}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::AdlAlert(res) => {
+                assert_eq!(res.data[0].adl_tt.to_string(), "-0.3")
+            }
+            res => panic!("unexpected: {:?}", res),
+        }
+
+        let content = r#"{"topic":"publicTrade.BTCUSDT","type":"snapshot","ts":1672304486868,"data":[{"T":1672304486865,"s":"BTCUSDT","S":"Buy","v":"0.001","p":"16578.50","L":"PlusTick","i":"20f43950-d8dd-5b31-9112-a178eb6023af","BT":false}]}"#;
+        match serde_json::from_str(content).unwrap() {
+            FuturePublicResponse::Trade(res) => assert_eq!(res.data[0].p.to_string(), "16578.50"),
+            res => panic!("unexpected: {:?}", res),
+        }
+    }
+}
@@ -1,17 +1,15 @@
-use super::callback::Callback;
 use super::response::SpotPublicResponseArg;
 use super::run;
-use super::HandlerFuture;
-use super::Subscriber;
+use super::Callback;
+use super::{Resubscriber, Subscriber};
 use crate::error::Result;
-use crate::KlineInterval;
-
-const MAINNET_SPOT: &str = "wss://stream.bybit.com/v5/public/spot";
-const TESTNET_SPOT: &str = "wss://stream-testnet.bybit.com/v5/public/spot";
+use crate::{Category, Environment, KlineInterval};
 
 pub enum OrderbookDepth {
     Level1,
     Level50,
+    Level200,
+    Level1000,
 }
 
 impl From<OrderbookDepth> for u16 {
@@ -20,6 +18,8 @@ impl From<OrderbookDepth> for u16 {
         match value {
             Level1 => 1,
             Level50 => 50,
+            Level200 => 200,
+            Level1000 => 1000,
         }
     }
 }
@@ -34,6 +34,11 @@ impl SpotWebsocketApiClient {
         self.subscriber.sub_orderbook(symbol.as_ref(), depth.into());
     }
 
+    /// Subscribe to the 50 levels orderbook with RPI (retail price improvement) orders.
+    pub fn subscribe_rpi_orderbook<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_rpi_orderbook(symbol.as_ref());
+    }
+
     pub fn subscribe_trade<S: AsRef<str>>(&mut self, symbol: S) {
         self.subscriber.sub_trade(symbol.as_ref());
     }
@@ -58,8 +63,14 @@ impl SpotWebsocketApiClient {
     pub fn subscribe_lt_nav<S: AsRef<str>>(&mut self, symbol: S) {
         self.subscriber.sub_lt_nav(symbol.as_ref());
     }
-    pub async fn run<'a>(&self, callback: Box<dyn FnMut(SpotPublicResponseArg) -> HandlerFuture + 'a + Send>) -> Result<()> {
-        run(&self.uri, self.subscriber.topics(), None, callback).await
+
+    /// A handle to resubscribe topics, e.g. the orderbook after a gap, while the client is running.
+    pub fn resubscriber(&self) -> Resubscriber {
+        self.subscriber.resubscriber()
+    }
+
+    pub async fn run<'a>(&self, callback: Callback<'a, SpotPublicResponseArg>) -> Result<()> {
+        run::<SpotPublicResponseArg>(&self.uri, &self.subscriber, None, callback).await
     }
 }
 
@@ -67,17 +78,28 @@ pub struct SpotWebSocketApiClientBuilder {
     uri: String,
 }
 
+impl Default for SpotWebSocketApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SpotWebSocketApiClientBuilder {
     /// Create a new `SpotWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
     pub fn new() -> Self {
         Self {
-            uri: MAINNET_SPOT.to_string(),
+            uri: Environment::Mainnet.public_ws(Category::Spot),
         }
     }
 
     /// Change channel URI to the testnet.
-    pub fn testnet(mut self) -> Self {
-        self.uri = TESTNET_SPOT.to_string();
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the spot channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.public_ws(Category::Spot);
         self
     }
 
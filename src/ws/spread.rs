@@ -0,0 +1,95 @@
+use super::response::SpreadPublicResponseArg;
+use super::run;
+use super::Callback;
+use super::{Resubscriber, Subscriber};
+use crate::error::Result;
+use crate::Environment;
+
+pub enum OrderbookDepth {
+    Level25,
+}
+
+impl From<OrderbookDepth> for u16 {
+    fn from(value: OrderbookDepth) -> Self {
+        use OrderbookDepth::*;
+        match value {
+            Level25 => 25,
+        }
+    }
+}
+
+pub struct SpreadWebsocketApiClient {
+    uri: String,
+    subscriber: Subscriber,
+}
+
+impl SpreadWebsocketApiClient {
+    /// Subscribe to the orderbook of a spread, e.g. `SOLUSDT_SOL/USDT`.
+    pub fn subscribe_orderbook<S: AsRef<str>>(&mut self, symbol: S, depth: OrderbookDepth) {
+        self.subscriber.sub_orderbook(symbol.as_ref(), depth.into());
+    }
+
+    pub fn subscribe_trade<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_trade(symbol.as_ref());
+    }
+
+    pub fn subscribe_ticker<S: AsRef<str>>(&mut self, symbol: S) {
+        self.subscriber.sub_ticker(symbol.as_ref());
+    }
+
+    /// A handle to resubscribe topics, e.g. the orderbook after a gap, while the client is running.
+    pub fn resubscriber(&self) -> Resubscriber {
+        self.subscriber.resubscriber()
+    }
+
+    pub async fn run<'a>(&self, callback: Callback<'a, SpreadPublicResponseArg>) -> Result<()> {
+        run::<SpreadPublicResponseArg>(&self.uri, &self.subscriber, None, callback).await
+    }
+}
+
+pub struct SpreadWebSocketApiClientBuilder {
+    uri: String,
+}
+
+impl Default for SpreadWebSocketApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpreadWebSocketApiClientBuilder {
+    /// Create a new `SpreadWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
+    pub fn new() -> Self {
+        Self {
+            uri: Environment::Mainnet.spread_ws(),
+        }
+    }
+
+    /// Change channel URI to the testnet.
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the spread channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.spread_ws();
+        self
+    }
+
+    /// Set channel URI to the URI specified.
+    ///
+    /// Note URI should **match** with api client kind.
+    /// Do not set a spot channel URI to a spread api client.
+    pub fn uri<S: AsRef<str>>(mut self, uri: S) -> Self {
+        self.uri = uri.as_ref().to_owned();
+        self
+    }
+
+    /// Build a spread websocket api client.
+    pub fn build(self) -> SpreadWebsocketApiClient {
+        SpreadWebsocketApiClient {
+            uri: self.uri,
+            subscriber: Subscriber::new(),
+        }
+    }
+}
@@ -0,0 +1,726 @@
+use futures::{SinkExt, StreamExt};
+use log::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use super::{auth_req, Credentials};
+use crate::enums::{Category, OrderType, Side, TimeInForce, TriggerBy};
+use crate::error::{BybitError, Result};
+pub use crate::order::OrderResult;
+use crate::time_sync::Clock;
+use crate::Environment;
+
+type Pending = Arc<Mutex<HashMap<String, oneshot::Sender<String>>>>;
+
+/// Parameters of `order.create`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderRequest {
+    /// Product type.
+    pub category: Category,
+    /// Symbol name.
+    pub symbol: String,
+    /// Side.
+    pub side: Side,
+    /// Order type.
+    pub order_type: OrderType,
+    /// Order qty.
+    pub qty: String,
+    /// Order price. Ignored by market orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    /// Time in force.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<TimeInForce>,
+    /// User customised order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+    /// Position index. 0: one-way mode, 1: hedge-mode Buy side, 2: hedge-mode Sell side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_idx: Option<u8>,
+    /// Trigger price of conditional orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<String>,
+    /// Trigger direction. 1: rise, 2: fall.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_direction: Option<u8>,
+    /// The price type of trigger price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_by: Option<TriggerBy>,
+    /// Take profit price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<String>,
+    /// Stop loss price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<String>,
+    /// Reduce only. `true` means reduce position size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_only: Option<bool>,
+    /// Close on trigger.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub close_on_trigger: Option<bool>,
+}
+
+impl CreateOrderRequest {
+    /// Create a limit order request.
+    pub fn limit<S: AsRef<str>>(
+        category: Category,
+        symbol: S,
+        side: Side,
+        qty: S,
+        price: S,
+    ) -> Self {
+        let mut req = Self::new(
+            category,
+            symbol.as_ref(),
+            side,
+            OrderType::Limit,
+            qty.as_ref(),
+        );
+        req.price = Some(price.as_ref().to_owned());
+        req
+    }
+
+    /// Create a market order request.
+    pub fn market<S: AsRef<str>>(category: Category, symbol: S, side: Side, qty: S) -> Self {
+        Self::new(
+            category,
+            symbol.as_ref(),
+            side,
+            OrderType::Market,
+            qty.as_ref(),
+        )
+    }
+
+    fn new(category: Category, symbol: &str, side: Side, order_type: OrderType, qty: &str) -> Self {
+        Self {
+            category,
+            symbol: symbol.to_owned(),
+            side,
+            order_type,
+            qty: qty.to_owned(),
+            price: None,
+            time_in_force: None,
+            order_link_id: None,
+            position_idx: None,
+            trigger_price: None,
+            trigger_direction: None,
+            trigger_by: None,
+            take_profit: None,
+            stop_loss: None,
+            reduce_only: None,
+            close_on_trigger: None,
+        }
+    }
+
+    /// Set the user customised order ID.
+    pub fn order_link_id<S: AsRef<str>>(mut self, order_link_id: S) -> Self {
+        self.order_link_id = Some(order_link_id.as_ref().to_owned());
+        self
+    }
+
+    /// Set the time in force.
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Set the position index.
+    pub fn position_idx(mut self, position_idx: u8) -> Self {
+        self.position_idx = Some(position_idx);
+        self
+    }
+
+    /// Set reduce only.
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = Some(reduce_only);
+        self
+    }
+}
+
+/// Parameters of `order.amend`. Either `order_id` or `order_link_id` is required.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendOrderRequest {
+    /// Product type.
+    pub category: Category,
+    /// Symbol name.
+    pub symbol: String,
+    /// Order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    /// User customised order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+    /// New order qty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<String>,
+    /// New order price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    /// New trigger price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<String>,
+    /// New take profit price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<String>,
+    /// New stop loss price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<String>,
+}
+
+/// Parameters of `order.cancel`. Either `order_id` or `order_link_id` is required.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelOrderRequest {
+    /// Product type.
+    pub category: Category,
+    /// Symbol name.
+    pub symbol: String,
+    /// Order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    /// User customised order ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_link_id: Option<String>,
+}
+
+impl AmendOrderRequest {
+    /// Create an amend request of an order of `symbol`.
+    pub fn new<S: AsRef<str>>(category: Category, symbol: S) -> Self {
+        Self {
+            category,
+            symbol: symbol.as_ref().to_owned(),
+            order_id: None,
+            order_link_id: None,
+            qty: None,
+            price: None,
+            trigger_price: None,
+            take_profit: None,
+            stop_loss: None,
+        }
+    }
+
+    /// Set the order ID.
+    pub fn order_id<S: AsRef<str>>(mut self, order_id: S) -> Self {
+        self.order_id = Some(order_id.as_ref().to_owned());
+        self
+    }
+
+    /// Set the user customised order ID.
+    pub fn order_link_id<S: AsRef<str>>(mut self, order_link_id: S) -> Self {
+        self.order_link_id = Some(order_link_id.as_ref().to_owned());
+        self
+    }
+
+    /// Set the new order qty.
+    pub fn qty<S: AsRef<str>>(mut self, qty: S) -> Self {
+        self.qty = Some(qty.as_ref().to_owned());
+        self
+    }
+
+    /// Set the new order price.
+    pub fn price<S: AsRef<str>>(mut self, price: S) -> Self {
+        self.price = Some(price.as_ref().to_owned());
+        self
+    }
+}
+
+impl CancelOrderRequest {
+    /// Create a cancel request of an order of `symbol`.
+    pub fn new<S: AsRef<str>>(category: Category, symbol: S) -> Self {
+        Self {
+            category,
+            symbol: symbol.as_ref().to_owned(),
+            order_id: None,
+            order_link_id: None,
+        }
+    }
+
+    /// Set the order ID.
+    pub fn order_id<S: AsRef<str>>(mut self, order_id: S) -> Self {
+        self.order_id = Some(order_id.as_ref().to_owned());
+        self
+    }
+
+    /// Set the user customised order ID.
+    pub fn order_link_id<S: AsRef<str>>(mut self, order_link_id: S) -> Self {
+        self.order_link_id = Some(order_link_id.as_ref().to_owned());
+        self
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct BatchRequest<'a, R> {
+    category: &'a Category,
+    request: &'a [R],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TradeRequest<'a, A> {
+    req_id: &'a str,
+    header: HashMap<&'static str, String>,
+    op: &'a str,
+    args: [A; 1],
+}
+
+/// The response of a trade request.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeResponse<T> {
+    /// The request ID.
+    pub req_id: String,
+    /// Result code. 0 means success.
+    pub ret_code: i32,
+    /// Result message.
+    pub ret_msg: String,
+    /// Operation, e.g. `order.create`.
+    pub op: String,
+    /// Business data.
+    pub data: T,
+    /// Per-order results of batch requests.
+    #[serde(default)]
+    pub ret_ext_info: RetExtInfo,
+    /// Rate limit headers, e.g. `X-Bapi-Limit-Status`.
+    #[serde(default)]
+    pub header: HashMap<String, String>,
+    /// Connection ID.
+    pub conn_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    req_id: Option<String>,
+    #[serde(default)]
+    ret_code: i32,
+    #[serde(default)]
+    ret_msg: String,
+    op: Option<String>,
+}
+
+/// The result of an order in a batch request.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOrderResult {
+    /// Product type.
+    pub category: Category,
+    /// Symbol name.
+    pub symbol: String,
+    /// Order ID.
+    pub order_id: String,
+    /// User customised order ID.
+    pub order_link_id: String,
+    /// Order created timestamp (ms). Only for `order.create-batch`.
+    pub create_at: Option<String>,
+}
+
+/// The result of a batch request.
+#[derive(Deserialize, Debug)]
+pub struct BatchResult {
+    pub list: Vec<BatchOrderResult>,
+}
+
+/// The per-order result codes of a batch request, in request order.
+#[derive(Deserialize, Debug, Default)]
+pub struct RetExtInfo {
+    #[serde(default)]
+    pub list: Vec<RetExtItem>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RetExtItem {
+    pub code: i32,
+    pub msg: String,
+}
+
+pub struct TradeWebsocketApiClient {
+    uri: String,
+    credentials: Credentials,
+    timeout: Duration,
+    recv_window: u64,
+    clock: Clock,
+}
+
+impl TradeWebsocketApiClient {
+    /// Connect and authenticate. The returned connection can be cloned to send requests concurrently.
+    pub async fn connect(&self) -> Result<TradeConnection> {
+        let (ws, _) = connect_async(&self.uri).await?;
+        let (mut sink, mut stream) = ws.split();
+
+        sink.send(Message::Text(auth_req(&self.credentials, &self.clock)?))
+            .await?;
+        let auth = tokio::time::timeout(self.timeout, stream.next())
+            .await
+            .map_err(|_| BybitError::Timeout)?
+            .ok_or(BybitError::ConnectionClosed)??;
+        let auth: Envelope = serde_json::from_str(auth.to_text()?)?;
+        if auth.ret_code != 0 {
+            return Err(BybitError::ApiError {
+                code: auth.ret_code,
+                msg: auth.ret_msg,
+            });
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let closed = Arc::new(AtomicBool::new(false));
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+
+        // Write requests and pings until the reader stops.
+        tokio::spawn(async move {
+            let mut ping = tokio::time::interval(Duration::from_secs(20));
+            loop {
+                let msg = tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                    _ = ping.tick() => Message::Text("{\"op\":\"ping\"}".to_string()),
+                    _ = &mut stop_rx => break,
+                };
+                if let Err(e) = sink.send(msg).await {
+                    error!("Error: {}", e);
+                    break;
+                }
+            }
+        });
+
+        // Route responses to requests by `reqId`.
+        let routes = pending.clone();
+        let reader_closed = closed.clone();
+        tokio::spawn(async move {
+            while let Some(Ok(msg)) = stream.next().await {
+                if let Message::Text(content) = msg {
+                    debug!("Received: {}", content);
+                    match req_id(&content) {
+                        Some(id) => {
+                            if let Some(tx) = routes.lock().unwrap().remove(&id) {
+                                let _ = tx.send(content);
+                            }
+                        }
+                        None => debug!("Unrouted: {}", content),
+                    }
+                }
+            }
+            // Dropping the senders fails all in-flight requests, and new requests
+            // fail fast once the flag is set under the same lock.
+            let mut routes = routes.lock().unwrap();
+            reader_closed.store(true, Ordering::Release);
+            routes.clear();
+            drop(routes);
+            let _ = stop_tx.send(());
+        });
+
+        Ok(TradeConnection {
+            inner: Arc::new(Inner {
+                tx,
+                pending,
+                closed,
+                next_id: AtomicU64::new(1),
+                timeout: self.timeout,
+                recv_window: self.recv_window,
+                clock: self.clock.clone(),
+            }),
+        })
+    }
+}
+
+struct Inner {
+    tx: mpsc::UnboundedSender<Message>,
+    pending: Pending,
+    closed: Arc<AtomicBool>,
+    next_id: AtomicU64,
+    timeout: Duration,
+    recv_window: u64,
+    clock: Clock,
+}
+
+/// An authenticated trade connection.
+#[derive(Clone)]
+pub struct TradeConnection {
+    inner: Arc<Inner>,
+}
+
+impl TradeConnection {
+    /// Place an order.
+    pub async fn create_order(
+        &self,
+        order: &CreateOrderRequest,
+    ) -> Result<TradeResponse<OrderResult>> {
+        self.request("order.create", order).await
+    }
+
+    /// Amend an unfilled or partially filled order.
+    pub async fn amend_order(
+        &self,
+        order: &AmendOrderRequest,
+    ) -> Result<TradeResponse<OrderResult>> {
+        self.request("order.amend", order).await
+    }
+
+    /// Cancel an unfilled or partially filled order.
+    pub async fn cancel_order(
+        &self,
+        order: &CancelOrderRequest,
+    ) -> Result<TradeResponse<OrderResult>> {
+        self.request("order.cancel", order).await
+    }
+
+    /// Place orders in batch. Check `ret_ext_info` for the result of each order.
+    pub async fn create_batch(
+        &self,
+        category: Category,
+        orders: &[CreateOrderRequest],
+    ) -> Result<TradeResponse<BatchResult>> {
+        self.batch("order.create-batch", &category, orders).await
+    }
+
+    /// Amend orders in batch. Check `ret_ext_info` for the result of each order.
+    pub async fn amend_batch(
+        &self,
+        category: Category,
+        orders: &[AmendOrderRequest],
+    ) -> Result<TradeResponse<BatchResult>> {
+        self.batch("order.amend-batch", &category, orders).await
+    }
+
+    /// Cancel orders in batch. Check `ret_ext_info` for the result of each order.
+    pub async fn cancel_batch(
+        &self,
+        category: Category,
+        orders: &[CancelOrderRequest],
+    ) -> Result<TradeResponse<BatchResult>> {
+        self.batch("order.cancel-batch", &category, orders).await
+    }
+
+    async fn batch<R, T>(
+        &self,
+        op: &str,
+        category: &Category,
+        request: &[R],
+    ) -> Result<TradeResponse<T>>
+    where
+        R: Serialize,
+        T: DeserializeOwned,
+    {
+        self.request(op, BatchRequest { category, request }).await
+    }
+
+    async fn request<A, T>(&self, op: &str, args: A) -> Result<TradeResponse<T>>
+    where
+        A: Serialize,
+        T: DeserializeOwned,
+    {
+        let id = self
+            .inner
+            .next_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string();
+        let req = request_message(&id, op, args, self.inner.recv_window, &self.inner.clock)?;
+
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut pending = self.inner.pending.lock().unwrap();
+            if self.inner.closed.load(Ordering::Acquire) {
+                return Err(BybitError::ConnectionClosed);
+            }
+            pending.insert(id.clone(), tx);
+        }
+        if self.inner.tx.send(Message::Text(req)).is_err() {
+            self.inner.pending.lock().unwrap().remove(&id);
+            return Err(BybitError::ConnectionClosed);
+        }
+
+        let content = match tokio::time::timeout(self.inner.timeout, rx).await {
+            Ok(Ok(content)) => content,
+            Ok(Err(_)) => return Err(BybitError::ConnectionClosed),
+            Err(_) => {
+                self.inner.pending.lock().unwrap().remove(&id);
+                return Err(BybitError::Timeout);
+            }
+        };
+
+        let envelope: Envelope = serde_json::from_str(&content)?;
+        if envelope.ret_code != 0 {
+            return Err(BybitError::ApiError {
+                code: envelope.ret_code,
+                msg: envelope.ret_msg,
+            });
+        }
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+fn request_message<A: Serialize>(
+    id: &str,
+    op: &str,
+    args: A,
+    recv_window: u64,
+    clock: &Clock,
+) -> Result<String> {
+    let header = HashMap::from([
+        ("X-BAPI-TIMESTAMP", clock.timestamp().to_string()),
+        ("X-BAPI-RECV-WINDOW", recv_window.to_string()),
+    ]);
+    let req = TradeRequest {
+        req_id: id,
+        header,
+        op,
+        args: [args],
+    };
+    Ok(serde_json::to_string(&req)?)
+}
+
+/// Extract `reqId` of order responses. Auth and pong responses are not routed.
+fn req_id(content: &str) -> Option<String> {
+    let envelope: Envelope = serde_json::from_str(content).ok()?;
+    match envelope.op.as_deref() {
+        Some("auth") | Some("pong") | Some("ping") => None,
+        _ => envelope.req_id,
+    }
+}
+
+pub struct TradeWebSocketApiClientBuilder {
+    uri: String,
+    timeout: Duration,
+    recv_window: u64,
+    clock: Clock,
+}
+
+impl Default for TradeWebSocketApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TradeWebSocketApiClientBuilder {
+    /// Create a new `TradeWebSocketApiClientBuilder`. Channel URI is set to the mainnet.
+    pub fn new() -> Self {
+        Self {
+            uri: Environment::Mainnet.trade_ws(),
+            timeout: Duration::from_secs(10),
+            recv_window: 5000,
+            clock: Clock::default(),
+        }
+    }
+
+    /// Change channel URI to the testnet.
+    pub fn testnet(self) -> Self {
+        self.env(Environment::Testnet)
+    }
+
+    /// Change channel URI to the trade channel of `env`.
+    pub fn env(mut self, env: Environment) -> Self {
+        self.uri = env.trade_ws();
+        self
+    }
+
+    /// Set channel URI to the URI specified.
+    ///
+    /// Note URI should **match** with api client kind.
+    /// Do not set a private channel URI to a trade api client.
+    pub fn uri<S: AsRef<str>>(mut self, uri: S) -> Self {
+        self.uri = uri.as_ref().to_owned();
+        self
+    }
+
+    /// Set the time to wait for a response of each request. Default is 10s.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the receive window (ms) of each request. Default is 5000.
+    pub fn recv_window(mut self, recv_window: u64) -> Self {
+        self.recv_window = recv_window;
+        self
+    }
+
+    /// Sign requests with `clock`, e.g. the clock synchronised by [`TimeSync`](crate::TimeSync).
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Build a trade websocket api client with api key and secret key.
+    pub fn build_with_credentials<S: AsRef<str>>(
+        self,
+        api_key: S,
+        secret: S,
+    ) -> TradeWebsocketApiClient {
+        self.build_with(Credentials::hmac(api_key, secret))
+    }
+
+    /// Build a trade websocket api client with HMAC or RSA credentials.
+    pub fn build_with(self, credentials: Credentials) -> TradeWebsocketApiClient {
+        TradeWebsocketApiClient {
+            uri: self.uri,
+            credentials,
+            timeout: self.timeout,
+            recv_window: self.recv_window,
+            clock: self.clock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_request_message() {
+        let order =
+            CreateOrderRequest::limit(Category::Linear, "ETHUSDT", Side::Buy, "0.2", "2800")
+                .time_in_force(TimeInForce::PostOnly);
+        let req = request_message("42", "order.create", &order, 8000, &Clock::default()).unwrap();
+        let req: Value = serde_json::from_str(&req).unwrap();
+        assert_eq!(req["reqId"], "42");
+        assert_eq!(req["op"], "order.create");
+        assert_eq!(req["header"]["X-BAPI-RECV-WINDOW"], "8000");
+        assert!(req["header"]["X-BAPI-TIMESTAMP"].is_string());
+        assert_eq!(
+            req["args"][0],
+            serde_json::json!({
+                "category": "linear",
+                "symbol": "ETHUSDT",
+                "side": "Buy",
+                "orderType": "Limit",
+                "qty": "0.2",
+                "price": "2800",
+                "timeInForce": "PostOnly",
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_response() {
+        let content = r#"{"reqId":"7","retCode":0,"retMsg":"OK","op":"order.create-batch","data":{"list":[{"category":"linear","symbol":"ETHUSDT","orderId":"","orderLinkId":"a","createAt":""}]},"retExtInfo":{"list":[{"code":10001,"msg":"position idx not match position mode"}]},"header":{"X-Bapi-Limit":"10"},"connId":"c"}"#;
+        assert_eq!(req_id(content).as_deref(), Some("7"));
+        let res: TradeResponse<BatchResult> = serde_json::from_str(content).unwrap();
+        assert_eq!(res.data.list[0].order_link_id, "a");
+        assert_eq!(res.ret_ext_info.list[0].code, 10001);
+        assert_eq!(res.conn_id.as_deref(), Some("c"));
+
+        let content = r#"{"reqId":"8","retCode":0,"retMsg":"OK","op":"order.create","data":{"orderId":"1","orderLinkId":""}}"#;
+        let res: TradeResponse<OrderResult> = serde_json::from_str(content).unwrap();
+        assert_eq!(res.conn_id, None);
+    }
+
+    #[test]
+    fn test_unrouted() {
+        let auth = r#"{"retCode":0,"retMsg":"OK","op":"auth","connId":"c"}"#;
+        assert_eq!(req_id(auth), None);
+        let pong = r#"{"retCode":0,"retMsg":"OK","op":"pong","data":["1"],"connId":"c"}"#;
+        assert_eq!(req_id(pong), None);
+    }
+}
@@ -1,22 +1,22 @@
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::SystemTime;
 
 use futures::future::BoxFuture;
 use futures::{SinkExt, StreamExt};
-use serde_json::{from_str, json};
+use serde::Deserialize;
+use serde_json::from_str;
+use thiserror::Error;
 use tokio::net::TcpStream;
+use tokio::time::timeout as tokio_timeout;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::{connect_async, MaybeTlsStream};
-use tokio::time::{timeout as tokio_timeout, sleep};
 use url::Url;
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
-use thiserror::Error;
 
+use crate::time_sync::Clock;
 use crate::ws::Op;
+use crate::{Category, Environment};
 
 use super::{auth_req, Credentials};
 
@@ -27,9 +27,6 @@ pub struct GateIOContentError {
     pub message: String,
 }
 
-const MAINNET_SPOT: &str = "wss://stream.bybit.com/v5/public/spot";
-const MAINNET_PRIVATE: &str = "wss://stream.bybit.com/v5/private";
-
 /// First errors are technical errors
 /// All unhandled gate-io content errors are GateIOError
 /// The rest are gate-io content errors that are properly handled
@@ -88,8 +85,6 @@ pub mod error_messages {
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-
-
 pub static ORDERBOOK_ENDPOINT: &str = "spot.order_book";
 type HandlerFuture = BoxFuture<'static, Result<()>>;
 
@@ -97,14 +92,20 @@ pub struct WebSockets<'a, WE> {
     pub socket: Option<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)>,
     handler: Box<dyn FnMut(WE) -> HandlerFuture + 'a + Send>,
     pub last_message_time: Option<std::time::SystemTime>,
-    pub timeout: Option<std::time::Duration>, 
+    pub timeout: Option<std::time::Duration>,
+    pub env: Environment,
+    /// The clock which signs the auth request.
+    pub clock: Clock,
 }
 
 impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
     /// New websocket holder with default configuration
     /// # Examples
     /// see examples/binance_websockets.rs
-    pub fn new<Callback>(handler: Callback, timeout: Option<std::time::Duration>) -> WebSockets<'a, WE>
+    pub fn new<Callback>(
+        handler: Callback,
+        timeout: Option<std::time::Duration>,
+    ) -> WebSockets<'a, WE>
     where
         Callback: FnMut(WE) -> HandlerFuture + 'a + Send,
     {
@@ -114,7 +115,10 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
     /// New websocket holder with provided configuration
     /// # Examples
     /// see examples/binance_websockets.rs
-    pub fn new_with_options<Callback>(handler: Callback, timeout: Option<std::time::Duration>) -> WebSockets<'a, WE>
+    pub fn new_with_options<Callback>(
+        handler: Callback,
+        timeout: Option<std::time::Duration>,
+    ) -> WebSockets<'a, WE>
     where
         Callback: FnMut(WE) -> HandlerFuture + 'a + Send,
     {
@@ -123,23 +127,22 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
             handler: Box::new(handler),
             last_message_time: None,
             timeout,
+            env: Environment::Mainnet,
+            clock: Clock::default(),
         }
     }
 
-  
-
-    /// Connect to a websocket endpoint
+    /// Connect to the spot channel of `env`
     pub async fn connect(&mut self) -> Result<()> {
-        let wss: String = format!("{}", MAINNET_SPOT.to_string());
+        let wss: String = self.env.public_ws(Category::Spot);
         println!("{:?}", wss);
         let url = Url::parse(&wss)?;
 
         self.handle_connect(url).await
     }
 
-
     pub async fn connect_private(&mut self) -> Result<()> {
-        let wss: String = format!("{}", MAINNET_PRIVATE.to_string());
+        let wss: String = self.env.private_ws();
         println!("{:?}", wss);
         let url = Url::parse(&wss)?;
 
@@ -156,24 +159,11 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         }
     }
 
-
-
-    pub async fn subscribe_orders(
-        &mut self,
-        credentials : &Credentials
-    ) -> Result<()> {
+    pub async fn subscribe_orders(&mut self, credentials: &Credentials) -> Result<()> {
         if let Some((ref mut socket, _)) = self.socket {
-            let start = SystemTime::now();
-            let since_the_epoch = start
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards");
-            let t = since_the_epoch.as_secs();
+            let req = auth_req(credentials, &self.clock).map_err(|e| Error::Msg(e.to_string()))?;
 
-            let req = auth_req(credentials);
-
-            socket
-            .send(Message::Text(req))
-            .await?;
+            socket.send(Message::Text(req)).await?;
 
             let topic = "order".to_string();
 
@@ -182,7 +172,6 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
                 args: vec![topic],
             };
 
-
             socket
                 .send(Message::Text(serde_json::to_string(&sub)?))
                 .await?;
@@ -192,25 +181,15 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
         }
     }
 
-    pub async fn subscribe_orderbook(
-        &mut self,
-        pair: String,
-        level: String
-    ) -> Result<()> {
+    pub async fn subscribe_orderbook(&mut self, pair: String, level: String) -> Result<()> {
         if let Some((ref mut socket, _)) = self.socket {
-            let start = SystemTime::now();
-            let since_the_epoch = start
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards");
-            let t = since_the_epoch.as_secs();
-
             let topic = format!("orderbook.{level}.{pair}");
 
             let sub = Op {
                 op: "subscribe",
                 args: vec![topic],
             };
-           
+
             socket
                 .send(Message::Text(serde_json::to_string(&sub)?))
                 .await?;
@@ -243,7 +222,9 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
                     // Check if we need to timeout due to inactivity
                     if let Some(last_message_time) = self.last_message_time {
                         let now = SystemTime::now();
-                        if now.duration_since(last_message_time)?.as_secs() > timeout_duration.as_secs() {
+                        if now.duration_since(last_message_time)?.as_secs()
+                            > timeout_duration.as_secs()
+                        {
                             println!("Timeout reached, closing connection.");
                             self.disconnect().await?;
                             break;
@@ -251,7 +232,6 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
                     }
                 }
 
-
                 let message = tokio_timeout(std::time::Duration::from_secs(5), socket.next()).await;
 
                 match message {
@@ -259,20 +239,16 @@ impl<'a, WE: serde::de::DeserializeOwned> WebSockets<'a, WE> {
                         self.last_message_time = Some(SystemTime::now()); // Update last message time
 
                         match msg {
-                            Message::Text(text) => {
-                                if !text.is_empty() {
-
-
-                                    if text.contains("subscribe") {
-                                        continue;
-                                    }
-            
-                                    if text.contains("auth") {
-                                        continue;
-                                    }
-                                    let event: WE = from_str(&text)?;
-                                    (self.handler)(event).await?;
+                            Message::Text(text) if !text.is_empty() => {
+                                if text.contains("subscribe") {
+                                    continue;
+                                }
+
+                                if text.contains("auth") {
+                                    continue;
                                 }
+                                let event: WE = from_str(&text)?;
+                                (self.handler)(event).await?;
                             }
                             Message::Ping(_) => {
                                 socket.send(Message::Pong(Vec::new())).await?;